@@ -0,0 +1,133 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::source::{code_mask, module_references, parse_mod_declaration, rewrite_crate_paths, strip_comments, strip_disabled_items};
+
+// #########################################
+// ##               Bundle                ##
+// #########################################
+// Library crate to inline into the bots, as seen from the bot sources
+pub struct Library {
+    pub name: String,
+    pub src_dir: PathBuf,
+}
+
+// Longest source the CodinGame arena accepts
+pub const MAX_SUBMISSION_LENGTH: usize = 100_000;
+
+// Builds a single compilable file out of a bot and the library it uses. The
+// library is inlined as a module named like the crate, so the bot's
+// `use <crate>::...` paths keep resolving unchanged. Only the modules the bot
+// reaches are inlined, without comments.
+pub fn bundle(bot_path: &Path, library: &Library) -> io::Result<String> {
+    let bot = strip_comments(&strip_disabled_items(&fs::read_to_string(bot_path)?));
+    let used = used_modules(&bot, library)?;
+    let library_source = inline_modules(&library.src_dir.join("lib.rs"), &library.src_dir, Some(&used))?;
+    let library_source = strip_comments(&rewrite_crate_paths(&library_source, &library.name));
+
+    let mut output = String::new();
+    output.push_str("// Generated by the PodRacers bundler, do not edit by hand.\n");
+    output.push_str(&format!("// Bot: {}\n", file_name(bot_path)));
+    output.push_str("#![allow(dead_code)]\n\n");
+    output.push_str(bot.trim_end());
+    output.push_str("\n\n");
+    output.push_str(&format!("pub mod {} {{\n", library.name));
+    output.push_str(library_source.trim_end());
+    output.push_str("\n}\n");
+
+    Ok(output)
+}
+
+// Top-level library modules the bot names, and those they name in turn
+fn used_modules(bot: &str, library: &Library) -> io::Result<BTreeSet<String>> {
+    let mut used = BTreeSet::new();
+    let mut pending = module_references(bot, &format!("{}::", library.name));
+    while let Some(name) = pending.pop() {
+        if !used.insert(name.clone()) {
+            continue;
+        }
+        let (module_path, module_children_dir) = find_module(&library.src_dir, &name)?;
+        let body = inline_modules(&module_path, &module_children_dir, None)?;
+        pending.extend(module_references(&body, "crate::"));
+    }
+    Ok(used)
+}
+
+// Reads a module file, replacing each `mod name;` by the inlined module body.
+// With `used`, the modules declared here but missing from it are left out.
+fn inline_modules(path: &Path, children_dir: &Path, used: Option<&BTreeSet<String>>) -> io::Result<String> {
+    let source = strip_disabled_items(&fs::read_to_string(path)?);
+    let mask = code_mask(&source);
+
+    let mut output = String::with_capacity(source.len());
+    let mut offset = 0;
+    for line in source.split_inclusive('\n') {
+        let code_start = offset + (line.len() - line.trim_start().len());
+        offset += line.len();
+
+        let declaration = match parse_mod_declaration(line) {
+            Some(declaration) if mask.get(code_start) == Some(&true) => declaration,
+            _ => {
+                output.push_str(line);
+                continue;
+            }
+        };
+
+        if used.is_some_and(|used| !used.contains(&declaration.name)) {
+            continue;
+        }
+        let (module_path, module_children_dir) = find_module(children_dir, &declaration.name)?;
+        let body = inline_modules(&module_path, &module_children_dir, None)?;
+        output.push_str(&format!(
+            "{}{}mod {} {{\n",
+            declaration.indent, declaration.visibility, declaration.name
+        ));
+        output.push_str(body.trim_end());
+        output.push_str(&format!("\n{}}}\n", declaration.indent));
+    }
+
+    Ok(output)
+}
+
+// Locates `name.rs` or `name/mod.rs`, along with where its own submodules live
+fn find_module(dir: &Path, name: &str) -> io::Result<(PathBuf, PathBuf)> {
+    let file = dir.join(format!("{}.rs", name));
+    if file.is_file() {
+        return Ok((file, dir.join(name)));
+    }
+    let mod_file = dir.join(name).join("mod.rs");
+    if mod_file.is_file() {
+        return Ok((mod_file, dir.join(name)));
+    }
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("module `{}` not found in {}", name, dir.display()),
+    ))
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundles_fit_in_a_submission() {
+        let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let library = Library {
+            name: "podracer_core".to_string(),
+            src_dir: workspace.join("podracer-core").join("src"),
+        };
+        for bot in &crate::BOTS {
+            let bundled = bundle(&workspace.join(bot), &library).unwrap();
+            let length = bundled.chars().count();
+            assert!(length <= MAX_SUBMISSION_LENGTH, "{} is {} characters long", bot, length);
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+mod bundle;
+mod source;
+
+use bundle::{bundle, Library, MAX_SUBMISSION_LENGTH};
+
+// Bots bundled when no explicit bot is given, relative to the workspace root
+const BOTS: [&str; 2] = ["single_pot.rs", "multiple_pods.rs"];
+const OUTPUT_DIR: &str = "dist";
+
+// Usage:
+//   bundler                       bundles every bot into dist/
+//   bundler <bot.rs> <output.rs>  bundles a single bot
+fn main() {
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let library = Library {
+        name: "podracer_core".to_string(),
+        src_dir: workspace.join("podracer-core").join("src"),
+    };
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let jobs: Vec<(PathBuf, PathBuf)> = match args.as_slice() {
+        [] => BOTS
+            .iter()
+            .map(|bot| (workspace.join(bot), workspace.join(OUTPUT_DIR).join(bot)))
+            .collect(),
+        [bot, output] => vec![(PathBuf::from(bot), PathBuf::from(output))],
+        _ => {
+            eprintln!("usage: bundler [<bot.rs> <output.rs>]");
+            process::exit(2);
+        }
+    };
+
+    for (bot, output) in jobs {
+        if let Err(error) = write_bundle(&bot, &output, &library) {
+            eprintln!("bundler: {}: {}", bot.display(), error);
+            process::exit(1);
+        }
+        eprintln!("bundled {} -> {}", bot.display(), output.display());
+    }
+}
+
+fn write_bundle(bot: &Path, output: &Path, library: &Library) -> std::io::Result<()> {
+    let bundled = bundle(bot, library)?;
+    let length = bundled.chars().count();
+    if length > MAX_SUBMISSION_LENGTH {
+        let message = format!("{} characters, over the {} the arena accepts", length, MAX_SUBMISSION_LENGTH);
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, message));
+    }
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(output, bundled)
+}
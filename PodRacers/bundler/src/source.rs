@@ -0,0 +1,541 @@
+// #########################################
+// ##            Source scanning          ##
+// #########################################
+// Marks which bytes of a Rust source are code, as opposed to comments, strings
+// or char literals, so braces and paths inside those are never touched.
+pub fn code_mask(src: &str) -> Vec<bool> {
+    let mut mask = vec![true; src.len()];
+    for span in literal_spans(src) {
+        for masked in &mut mask[span.start..span.end] {
+            *masked = false;
+        }
+    }
+    mask
+}
+
+// Byte range of a comment, string or char literal
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub comment: bool,
+}
+
+// Comments, strings and char literals of a Rust source, in order
+pub fn literal_spans(src: &str) -> Vec<Span> {
+    let bytes = src.as_bytes();
+    let mut spans = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let start = i;
+        let comment = bytes[i..].starts_with(b"//") || bytes[i..].starts_with(b"/*");
+        if bytes[i..].starts_with(b"//") {
+            while i < bytes.len() && bytes[i] != b'\n' {
+                i += 1;
+            }
+        } else if bytes[i..].starts_with(b"/*") {
+            let mut depth = 0;
+            while i < bytes.len() {
+                if bytes[i..].starts_with(b"/*") {
+                    depth += 1;
+                    i += 2;
+                } else if bytes[i..].starts_with(b"*/") {
+                    depth -= 1;
+                    i += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    i += 1;
+                }
+            }
+        } else if let Some(hashes) = raw_string_start(bytes, i) {
+            // r"..", r#".."#, br".."
+            i += bytes[i..].iter().position(|&b| b == b'"').unwrap() + 1;
+            while i < bytes.len() {
+                if bytes[i] == b'"' && bytes[i + 1..].iter().take(hashes).filter(|&&b| b == b'#').count() == hashes {
+                    i += 1 + hashes;
+                    break;
+                }
+                i += 1;
+            }
+        } else if bytes[i] == b'"' {
+            i += 1;
+            while i < bytes.len() && bytes[i] != b'"' {
+                i += if bytes[i] == b'\\' { 2 } else { 1 };
+            }
+            i += 1;
+        } else if bytes[i] == b'\'' {
+            match char_literal_len(src, i) {
+                Some(len) => i += len,
+                // A lifetime or a label, which is code
+                None => {
+                    i += 1;
+                    continue;
+                }
+            }
+        } else {
+            i += 1;
+            continue;
+        }
+
+        let end = i.min(bytes.len());
+        spans.push(Span { start, end, comment });
+        i = end;
+    }
+
+    spans
+}
+
+fn is_ident_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
+
+// Number of '#' of a raw string starting at `i`, if there is one
+fn raw_string_start(bytes: &[u8], i: usize) -> Option<usize> {
+    if i > 0 && is_ident_byte(bytes[i - 1]) {
+        return None;
+    }
+    let mut j = i;
+    if bytes[j] == b'b' {
+        j += 1;
+    }
+    if bytes.get(j) != Some(&b'r') {
+        return None;
+    }
+    j += 1;
+    let hashes = bytes[j..].iter().take_while(|&&b| b == b'#').count();
+    match bytes.get(j + hashes) {
+        Some(b'"') => Some(hashes),
+        _ => None,
+    }
+}
+
+// Length of the char literal starting at `i`, or None when the quote opens a lifetime
+fn char_literal_len(src: &str, i: usize) -> Option<usize> {
+    let bytes = src.as_bytes();
+    if bytes.get(i + 1) == Some(&b'\\') {
+        let close = bytes[i + 3..].iter().position(|&b| b == b'\'')?;
+        return Some(close + 4);
+    }
+    let character = src[i + 1..].chars().next()?;
+    let after = i + 1 + character.len_utf8();
+    match bytes.get(after) {
+        Some(b'\'') => Some(after + 1 - i),
+        _ => None,
+    }
+}
+
+// #########################################
+// ##            Item stripping           ##
+// #########################################
+// Removes every item, attribute included, that a `#[cfg(..)]` leaves out of
+// the bundled build: tests, and code behind any cargo feature, none being
+// enabled when the bundle is compiled on its own.
+pub fn strip_disabled_items(src: &str) -> String {
+    const ATTRIBUTE: &str = "#[cfg(";
+
+    let mut output = src.to_string();
+    let mut from = 0;
+    loop {
+        let mask = code_mask(&output);
+        let bytes = output.as_bytes();
+        let found = output[from..]
+            .match_indices(ATTRIBUTE)
+            .map(|(index, _)| from + index)
+            .find(|&index| mask[index]);
+        let attribute = match found {
+            Some(attribute) => attribute,
+            None => return output,
+        };
+
+        // The predicate runs to the parenthesis closing `cfg(`
+        let predicate_start = attribute + ATTRIBUTE.len();
+        let mut end = predicate_start;
+        let mut depth = 1;
+        while end < bytes.len() && depth > 0 {
+            if mask[end] {
+                match bytes[end] {
+                    b'(' => depth += 1,
+                    b')' => depth -= 1,
+                    _ => {}
+                }
+            }
+            end += 1;
+        }
+        if cfg_value(&output[predicate_start..end - 1]) != Some(false) {
+            from = end;
+            continue;
+        }
+
+        // Then the item, past the attribute's closing bracket
+        end += 1;
+        let mut depth = 0;
+        while end < bytes.len() {
+            let byte = bytes[end];
+            end += 1;
+            if !mask[end - 1] {
+                continue;
+            }
+            // Brackets and parentheses count too, for the `;` of `[u8; 2]`
+            match byte {
+                b'{' | b'[' | b'(' => depth += 1,
+                b']' | b')' => depth -= 1,
+                b'}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                b';' if depth == 0 => break,
+                _ => {}
+            }
+        }
+
+        // Take the whole lines, so no blank indentation is left behind
+        let line_start = output[..attribute].rfind('\n').map(|index| index + 1).unwrap_or(0);
+        if output[line_start..attribute].trim().is_empty() {
+            let end = if output[end..].starts_with('\n') { end + 1 } else { end };
+            output.replace_range(line_start..end, "");
+            from = line_start;
+        } else {
+            output.replace_range(attribute..end, "");
+            from = attribute;
+        }
+    }
+}
+
+// Value of a cfg predicate in the bundled build, where `test` and every
+// feature are off. None when it depends on anything else.
+fn cfg_value(predicate: &str) -> Option<bool> {
+    let predicate = predicate.trim();
+    if predicate == "test" || predicate.strip_prefix("feature").is_some_and(|rest| rest.trim_start().starts_with('=')) {
+        return Some(false);
+    }
+
+    let open = predicate.find('(')?;
+    let arguments = predicate[open + 1..].strip_suffix(')')?;
+    let mut values = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, byte) in arguments.bytes().enumerate() {
+        match byte {
+            b'(' => depth += 1,
+            b')' => depth -= 1,
+            b',' if depth == 0 => {
+                values.push(cfg_value(&arguments[start..index]));
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    // A trailing comma leaves nothing after it
+    if !arguments[start..].trim().is_empty() {
+        values.push(cfg_value(&arguments[start..]));
+    }
+
+    match predicate[..open].trim() {
+        "not" if values.len() == 1 => values[0].map(|value| !value),
+        "all" if values.contains(&Some(false)) => Some(false),
+        "all" if values.iter().all(|value| *value == Some(true)) => Some(true),
+        "any" if values.contains(&Some(true)) => Some(true),
+        "any" if values.iter().all(|value| *value == Some(false)) => Some(false),
+        _ => None,
+    }
+}
+
+// #########################################
+// ##            Path rewriting           ##
+// #########################################
+// Prefixes `crate::` paths with the module the library is inlined into.
+pub fn rewrite_crate_paths(src: &str, module: &str) -> String {
+    const CRATE: &str = "crate::";
+
+    let mask = code_mask(src);
+    let bytes = src.as_bytes();
+    let mut output = String::with_capacity(src.len());
+    let mut last = 0;
+
+    for (index, _) in src.match_indices(CRATE) {
+        let preceded = index > 0 && (is_ident_byte(bytes[index - 1]) || bytes[index - 1] == b'$');
+        if !mask[index] || preceded {
+            continue;
+        }
+        output.push_str(&src[last..index]);
+        output.push_str(CRATE);
+        output.push_str(module);
+        output.push_str("::");
+        last = index + CRATE.len();
+    }
+    output.push_str(&src[last..]);
+
+    output
+}
+
+// #########################################
+// ##           Comment stripping         ##
+// #########################################
+// Drops comments, trailing spaces and blank lines, to keep the bundle short.
+// Line breaks inside string literals are left alone.
+pub fn strip_comments(src: &str) -> String {
+    let mut code = String::with_capacity(src.len());
+    let mut last = 0;
+    for span in literal_spans(src).iter().filter(|span| span.comment) {
+        code.push_str(&src[last..span.start]);
+        // A block comment may be all that separates two tokens
+        if src[span.start..].starts_with("/*") {
+            code.push(' ');
+        }
+        last = span.end;
+    }
+    code.push_str(&src[last..]);
+
+    let mask = code_mask(&code);
+    let mut output = String::with_capacity(code.len());
+    let mut offset = 0;
+    for line in code.split_inclusive('\n') {
+        let line_end = offset + line.len() - 1;
+        offset += line.len();
+        if !line.ends_with('\n') || !mask[line_end] {
+            output.push_str(line);
+        } else if !line.trim().is_empty() {
+            output.push_str(line.trim_end());
+            output.push('\n');
+        }
+    }
+    output
+}
+
+// #########################################
+// ##          Module references          ##
+// #########################################
+// Top-level modules named by the source's `<prefix>module` paths, `prefix`
+// being `crate::` or the library's name, `use` groups included.
+pub fn module_references(src: &str, prefix: &str) -> Vec<String> {
+    let mask = code_mask(src);
+    let bytes = src.as_bytes();
+    let mut modules = Vec::new();
+
+    for (index, _) in src.match_indices(prefix) {
+        let preceded = index > 0 && (is_ident_byte(bytes[index - 1]) || bytes[index - 1] == b'$');
+        if !mask[index] || preceded {
+            continue;
+        }
+        let rest = &src[index + prefix.len()..];
+        match rest.strip_prefix('{') {
+            Some(group) => {
+                let mut depth = 0;
+                let end = group
+                    .bytes()
+                    .position(|byte| {
+                        match byte {
+                            b'{' => depth += 1,
+                            b'}' => depth -= 1,
+                            _ => {}
+                        }
+                        depth < 0
+                    })
+                    .unwrap_or(group.len());
+                // Each item of the group starts with the module name
+                let mut depth = 0;
+                let mut item_start = true;
+                for (offset, byte) in group[..end].bytes().enumerate() {
+                    match byte {
+                        b'{' => depth += 1,
+                        b'}' => depth -= 1,
+                        b',' if depth == 0 => item_start = true,
+                        _ if item_start && is_ident_byte(byte) => {
+                            modules.push(leading_ident(&group[offset..]).to_string());
+                            item_start = false;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            None => modules.push(leading_ident(rest).to_string()),
+        }
+    }
+
+    modules.retain(|module| !module.is_empty() && module != "self");
+    modules.sort();
+    modules.dedup();
+    modules
+}
+
+fn leading_ident(src: &str) -> &str {
+    let end = src.bytes().position(|byte| !is_ident_byte(byte)).unwrap_or(src.len());
+    &src[..end]
+}
+
+// #########################################
+// ##          Module declarations        ##
+// #########################################
+pub struct ModDeclaration {
+    pub indent: String,
+    pub visibility: String,
+    pub name: String,
+}
+
+// Recognizes an out-of-line `mod name;` declaration
+pub fn parse_mod_declaration(line: &str) -> Option<ModDeclaration> {
+    let trimmed = line.trim_start();
+    let indent = line[..line.len() - trimmed.len()].to_string();
+    let declaration = trimmed.trim_end().strip_suffix(';')?;
+
+    let (visibility, rest) = match declaration.find("mod ") {
+        Some(index) => (&declaration[..index], &declaration[index + "mod ".len()..]),
+        None => return None,
+    };
+    let visibility_ok = visibility.is_empty() || (visibility.starts_with("pub") && visibility.ends_with(' '));
+    let name = rest.trim();
+    if !visibility_ok || name.is_empty() || !name.bytes().all(is_ident_byte) {
+        return None;
+    }
+
+    Some(ModDeclaration {
+        indent,
+        visibility: visibility.to_string(),
+        name: name.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The source with everything but code blanked out
+    fn code(src: &str) -> String {
+        src.chars().zip(code_mask(src)).map(|(c, code)| if code { c } else { '_' }).collect()
+    }
+
+    #[test]
+    fn masks_comments_strings_and_chars() {
+        assert_eq!(code("a // b\nc /* d /* e */ f */ g"), "a ____\nc _________________ g");
+        assert_eq!(code(r#"x = "a \"}\" b"; y"#), r#"x = ___________; y"#);
+        assert_eq!(code("c = '{'; d = '\\''; e"), "c = ___; d = ____; e");
+    }
+
+    #[test]
+    fn masks_raw_strings() {
+        assert_eq!(code(r###"a = r"}"; b"###), r###"a = ____; b"###);
+        assert_eq!(code(r###"a = r#"say "}" here"#; b"###), r###"a = _________________; b"###);
+        assert_eq!(code(r###"a = br"{"; b"###), r###"a = _____; b"###);
+        // Not a raw string, just an identifier ending in r
+        assert_eq!(code(r#"for"x""#), r#"for___"#);
+    }
+
+    #[test]
+    fn tells_lifetimes_from_char_literals() {
+        assert_eq!(code("fn f<'a>(x: &'a str) -> char { 'a' }"), "fn f<'a>(x: &'a str) -> char { ___ }");
+        assert_eq!(code("'outer: loop { break 'outer; }"), "'outer: loop { break 'outer; }");
+    }
+
+    #[test]
+    fn strips_a_test_module_with_braces_in_literals() {
+        let src = "\
+fn kept() {}
+
+#[cfg(test)]
+mod tests {
+    const OPEN: &str = \"{\";
+    const CLOSE: char = '}';
+    // }
+    /* { */
+    fn raw() -> &'static str { r#\"}\"# }
+}
+
+fn also_kept() {}
+";
+        assert_eq!(strip_disabled_items(src), "fn kept() {}\n\n\nfn also_kept() {}\n");
+    }
+
+    #[test]
+    fn strips_items_ending_past_brackets() {
+        let src = "#[cfg(test)]\nconst PAIR: [u8; 2] = [1, 2];\n#[cfg(test)]\nfn f(x: (u8, u8)) {}\nfn kept() {}\n";
+        assert_eq!(strip_disabled_items(src), "fn kept() {}\n");
+    }
+
+    #[test]
+    fn leaves_the_attribute_inside_literals() {
+        let src = "const ATTRIBUTE: &str = \"#[cfg(test)]\"; // #[cfg(test)]\n";
+        assert_eq!(strip_disabled_items(src), src);
+    }
+
+    #[test]
+    fn gives_the_same_output_on_a_second_run() {
+        let src = "use crate::a;\n#[cfg(test)]\nmod tests {\n    use crate::b;\n}\nfn f() {}\n";
+        let stripped = strip_disabled_items(src);
+        assert_eq!(strip_disabled_items(&stripped), stripped);
+        assert_eq!(rewrite_crate_paths(&stripped, "lib"), rewrite_crate_paths(&stripped, "lib"));
+        assert_eq!(rewrite_crate_paths(&stripped, "lib"), "use crate::lib::a;\nfn f() {}\n");
+    }
+
+    #[test]
+    fn strips_items_behind_features() {
+        let src = "\
+#[cfg(feature = \"f64\")]
+pub type Scalar = f64;
+#[cfg(not(feature = \"f64\"))]
+pub type Scalar = f32;
+#[cfg(all(feature = \"a\", not(feature = \"b\")))]
+fn a() {}
+#[cfg(not(any(
+    feature = \"a\",
+    feature = \"b\",
+)))]
+fn neither() {}
+#[cfg(target_os = \"linux\")]
+fn linux() {}
+";
+        let expected = "\
+#[cfg(not(feature = \"f64\"))]
+pub type Scalar = f32;
+#[cfg(not(any(
+    feature = \"a\",
+    feature = \"b\",
+)))]
+fn neither() {}
+#[cfg(target_os = \"linux\")]
+fn linux() {}
+";
+        assert_eq!(strip_disabled_items(src), expected);
+    }
+
+    #[test]
+    fn strips_comments_and_blank_lines_only() {
+        let src = "// Header\n\nfn f() { // trailing\n    let s = \"// kept\n\n\";\n    a/* gap */b\n}\n";
+        assert_eq!(strip_comments(src), "fn f() {\n    let s = \"// kept\n\n\";\n    a b\n}\n");
+    }
+
+    #[test]
+    fn lists_the_modules_a_source_names() {
+        let src = "use lib::a::X;\nuse lib::{b::Y, c, self};\nlet s = \"lib::d\";\nmy_lib::e::f();\nlib::a::g();\n";
+        assert_eq!(module_references(src, "lib::"), ["a", "b", "c"]);
+        assert_eq!(module_references("use crate::{x::{Y, Z}, w};", "crate::"), ["w", "x"]);
+    }
+
+    #[test]
+    fn rewrites_crate_paths_in_code_only() {
+        let src = "use crate::a;\nlet s = \"crate::b\";\n// crate::c\nmy_crate::d;\n$crate::e;\n";
+        assert_eq!(
+            rewrite_crate_paths(src, "lib"),
+            "use crate::lib::a;\nlet s = \"crate::b\";\n// crate::c\nmy_crate::d;\n$crate::e;\n"
+        );
+    }
+
+    #[test]
+    fn parses_mod_declarations() {
+        let declaration = parse_mod_declaration("    pub(crate) mod x;\n").unwrap();
+        assert_eq!(declaration.indent, "    ");
+        assert_eq!(declaration.visibility, "pub(crate) ");
+        assert_eq!(declaration.name, "x");
+
+        let declaration = parse_mod_declaration("mod y;").unwrap();
+        assert_eq!(declaration.visibility, "");
+        assert_eq!(declaration.name, "y");
+
+        assert!(parse_mod_declaration("mod tests {").is_none());
+        assert!(parse_mod_declaration("let mod_x = mod y;").is_none());
+        assert!(parse_mod_declaration("mod a::b;").is_none());
+    }
+}
@@ -0,0 +1,168 @@
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::vector::{Scalar, PI};
+
+// #########################################
+// ##                Angles               ##
+// #########################################
+// The game speaks degrees and trigonometry radians. Keeping them apart as two
+// types turns any mix-up into a compile error: going from one to the other
+// takes an explicit conversion.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Degrees(pub Scalar);
+impl Degrees {
+    pub fn to_radians(self) -> Radians {
+        Radians(self.0.to_radians())
+    }
+    pub fn abs(self) -> Degrees {
+        Degrees(self.0.abs())
+    }
+    pub fn clamp(self, min: Degrees, max: Degrees) -> Degrees {
+        Degrees(self.0.clamp(min.0, max.0))
+    }
+    // Same angle within (-180, 180]
+    pub fn normalized(self) -> Degrees {
+        Degrees(wrap(self.0, 180.0))
+    }
+    // Same angle within [0, 360), the way the game gives headings
+    pub fn heading(self) -> Degrees {
+        // Adding 0 turns -0 into 0
+        let angle = self.0 % 360.0;
+        let angle = if angle < 0.0 { angle + 360.0 } else { angle + 0.0 };
+        // A tiny negative angle rounds up to a whole turn
+        Degrees(if angle >= 360.0 { 0.0 } else { angle })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Radians(pub Scalar);
+impl Radians {
+    pub fn to_degrees(self) -> Degrees {
+        Degrees(self.0.to_degrees())
+    }
+    pub fn abs(self) -> Radians {
+        Radians(self.0.abs())
+    }
+    pub fn cos(self) -> Scalar {
+        self.0.cos()
+    }
+    pub fn sin(self) -> Scalar {
+        self.0.sin()
+    }
+    // Same angle within (-PI, PI]
+    pub fn normalized(self) -> Radians {
+        Radians(wrap(self.0, PI))
+    }
+}
+
+// Angle within (-half_turn, half_turn]
+fn wrap(angle: Scalar, half_turn: Scalar) -> Scalar {
+    let angle = angle % (2.0 * half_turn);
+    if angle > half_turn {
+        angle - 2.0 * half_turn
+    } else if angle <= -half_turn {
+        angle + 2.0 * half_turn
+    } else {
+        angle + 0.0
+    }
+}
+
+// +--------------------------+
+// |        Arithmetic        |
+// +--------------------------+
+// Within a unit only. Dividing two angles gives their ratio.
+macro_rules! angle_arithmetic {
+    ($unit:ident) => {
+        impl Add for $unit {
+            type Output = $unit;
+            fn add(self, other: $unit) -> $unit {
+                $unit(self.0 + other.0)
+            }
+        }
+        impl Sub for $unit {
+            type Output = $unit;
+            fn sub(self, other: $unit) -> $unit {
+                $unit(self.0 - other.0)
+            }
+        }
+        impl Neg for $unit {
+            type Output = $unit;
+            fn neg(self) -> $unit {
+                $unit(-self.0)
+            }
+        }
+        impl Mul<Scalar> for $unit {
+            type Output = $unit;
+            fn mul(self, factor: Scalar) -> $unit {
+                $unit(self.0 * factor)
+            }
+        }
+        impl Div<Scalar> for $unit {
+            type Output = $unit;
+            fn div(self, divisor: Scalar) -> $unit {
+                $unit(self.0 / divisor)
+            }
+        }
+        impl Div for $unit {
+            type Output = Scalar;
+            fn div(self, other: $unit) -> Scalar {
+                self.0 / other.0
+            }
+        }
+        impl fmt::Display for $unit {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+    };
+}
+angle_arithmetic!(Degrees);
+angle_arithmetic!(Radians);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_into_half_open_ranges() {
+        assert_eq!(wrap(-180.0, 180.0), 180.0);
+        assert_eq!(wrap(180.0, 180.0), 180.0);
+        assert_eq!(wrap(540.0, 180.0), 180.0);
+        assert_eq!(wrap(-540.0, 180.0), 180.0);
+        assert_eq!(wrap(190.0, 180.0), -170.0);
+        assert_eq!(wrap(-190.0, 180.0), 170.0);
+        assert!(wrap(-0.0, 180.0).is_sign_positive());
+    }
+
+    #[test]
+    fn normalizes_both_units() {
+        assert_eq!(Degrees(-180.0).normalized(), Degrees(180.0));
+        assert_eq!(Degrees(540.0).normalized(), Degrees(180.0));
+        assert_eq!(Degrees(-540.0).normalized(), Degrees(180.0));
+        assert_eq!(Degrees(350.0).normalized(), Degrees(-10.0));
+        assert_eq!(Degrees(-0.0).normalized().to_string(), "0");
+        assert_eq!(Radians(-PI).normalized(), Radians(PI));
+        assert!((Radians(3.0 * PI).normalized().0 - PI).abs() < 1e-6);
+    }
+
+    #[test]
+    fn gives_headings_within_a_turn() {
+        assert_eq!(Degrees(-90.0).heading(), Degrees(270.0));
+        assert_eq!(Degrees(-180.0).heading(), Degrees(180.0));
+        assert_eq!(Degrees(540.0).heading(), Degrees(180.0));
+        assert_eq!(Degrees(-540.0).heading(), Degrees(180.0));
+        assert_eq!(Degrees(360.0).heading(), Degrees(0.0));
+        assert_eq!(Degrees(-0.0).heading().to_string(), "0");
+        assert!(Degrees(-1e-7).heading() < Degrees(360.0));
+    }
+
+    #[test]
+    fn converts_between_units() {
+        assert!((Degrees(180.0).to_radians().0 - PI).abs() < 1e-6);
+        assert!((Degrees(-90.0).to_radians().0 + PI / 2.0).abs() < 1e-6);
+        assert!((Radians(PI / 2.0).to_degrees().0 - 90.0).abs() < 1e-4);
+        assert!((Degrees(37.5).to_radians().to_degrees().0 - 37.5).abs() < 1e-4);
+        assert_eq!(Degrees(90.0) / Degrees(18.0), 5.0);
+    }
+}
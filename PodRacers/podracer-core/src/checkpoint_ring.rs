@@ -0,0 +1,102 @@
+use std::ops::Index;
+use std::slice;
+
+use crate::vector::Vector;
+
+// #########################################
+// ##           CheckpointRing            ##
+// #########################################
+// Checkpoints of a lap, looked up with wraparound: after the last one comes
+// checkpoint 0 again.
+#[derive(Clone, Debug, Default)]
+pub struct CheckpointRing {
+    checkpoints: Vec<Vector>,
+}
+impl CheckpointRing {
+    pub fn new(checkpoints: Vec<Vector>) -> CheckpointRing {
+        CheckpointRing { checkpoints }
+    }
+    pub fn len(&self) -> usize {
+        self.checkpoints.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.checkpoints.is_empty()
+    }
+    pub fn iter(&self) -> slice::Iter<'_, Vector> {
+        self.checkpoints.iter()
+    }
+
+    // Index of the checkpoint `k` gates after checkpoint `index`
+    pub fn next_index(&self, index: usize, k: usize) -> usize {
+        (index + k) % self.checkpoints.len()
+    }
+    pub fn next(&self, index: usize, k: usize) -> &Vector {
+        &self.checkpoints[self.next_index(index, k)]
+    }
+
+    // +--------------------------+
+    // |         Learning         |
+    // +--------------------------+
+    // For maps discovered while racing, as in the single-pod game
+    pub fn push(&mut self, checkpoint: Vector) {
+        self.checkpoints.push(checkpoint);
+    }
+    pub fn position(&self, checkpoint: &Vector) -> Option<usize> {
+        self.checkpoints.iter().position(|known| known == checkpoint)
+    }
+    pub fn contains(&self, checkpoint: &Vector) -> bool {
+        self.position(checkpoint).is_some()
+    }
+}
+impl Index<usize> for CheckpointRing {
+    type Output = Vector;
+
+    fn index(&self, index: usize) -> &Vector {
+        &self.checkpoints[index]
+    }
+}
+impl<'a> IntoIterator for &'a CheckpointRing {
+    type Item = &'a Vector;
+    type IntoIter = slice::Iter<'a, Vector>;
+
+    fn into_iter(self) -> slice::Iter<'a, Vector> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ring() -> CheckpointRing {
+        CheckpointRing::new(vec![Vector::new(0.0, 0.0), Vector::new(1.0, 0.0), Vector::new(2.0, 0.0)])
+    }
+
+    #[test]
+    fn wraps_around_after_the_last_checkpoint() {
+        let ring = ring();
+        assert_eq!(ring.next(2, 1), &ring[0]);
+        assert_eq!(ring.next(1, 2), &ring[0]);
+        assert_eq!(ring.next(0, 0), &ring[0]);
+        assert_eq!(ring.next_index(2, 2), 1);
+    }
+
+    #[test]
+    fn wraps_whole_laps() {
+        let ring = ring();
+        assert_eq!(ring.next_index(0, 3), 0);
+        assert_eq!(ring.next_index(1, 7), 2);
+        assert_eq!(ring.next(2, 6), &ring[2]);
+    }
+
+    #[test]
+    fn learns_checkpoints_in_order() {
+        let mut ring = CheckpointRing::default();
+        assert!(ring.is_empty());
+        ring.push(Vector::new(5.0, 5.0));
+        ring.push(Vector::new(9.0, 1.0));
+        assert_eq!(ring.position(&Vector::new(9.0, 1.0)), Some(1));
+        assert!(!ring.contains(&Vector::new(1.0, 9.0)));
+        assert_eq!(ring.next(1, 1), &Vector::new(5.0, 5.0));
+    }
+}
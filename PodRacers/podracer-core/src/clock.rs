@@ -0,0 +1,87 @@
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+// #########################################
+// ##              TurnClock              ##
+// #########################################
+// Response time allowed by CodinGame
+pub const FIRST_TURN_LIMIT: Duration = Duration::from_millis(1000);
+pub const TURN_LIMIT: Duration = Duration::from_millis(75);
+// Time kept aside for output and scheduling jitter
+pub const FIRST_TURN_MARGIN: Duration = Duration::from_millis(100);
+pub const TURN_MARGIN: Duration = Duration::from_millis(15);
+
+// Wall clock of the current turn. Started when the first input line of the
+// turn arrives, then polled by the search so it stops before the deadline.
+pub struct TurnClock {
+    first_turn_margin: Duration,
+    margin: Duration,
+    start: Instant,
+    // Turns started so far, the current one included
+    turn: usize,
+    // Search iterations done this turn
+    iterations: Cell<usize>,
+}
+impl TurnClock {
+    pub fn new() -> TurnClock {
+        TurnClock::with_margins(FIRST_TURN_MARGIN, TURN_MARGIN)
+    }
+    pub fn with_margins(first_turn_margin: Duration, margin: Duration) -> TurnClock {
+        TurnClock {
+            first_turn_margin,
+            margin,
+            start: Instant::now(),
+            turn: 0,
+            iterations: Cell::new(0),
+        }
+    }
+
+    pub fn start_turn(&mut self) {
+        self.start = Instant::now();
+        self.turn += 1;
+        self.iterations.set(0);
+    }
+    pub fn is_first_turn(&self) -> bool {
+        self.turn <= 1
+    }
+
+    // Time the search may use this turn, once the margin is taken out
+    pub fn budget(&self) -> Duration {
+        if self.is_first_turn() {
+            FIRST_TURN_LIMIT.saturating_sub(self.first_turn_margin)
+        } else {
+            TURN_LIMIT.saturating_sub(self.margin)
+        }
+    }
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+    pub fn remaining(&self) -> Duration {
+        self.budget().saturating_sub(self.elapsed())
+    }
+    pub fn is_over(&self) -> bool {
+        self.elapsed() >= self.budget()
+    }
+
+    // Counts one search iteration, for the report
+    pub fn tick(&self) {
+        self.iterations.set(self.iterations.get() + 1);
+    }
+    pub fn iterations(&self) -> usize {
+        self.iterations.get()
+    }
+    pub fn report(&self) {
+        eprintln!(
+            "turn {}: {} iterations in {} ms (budget {} ms)",
+            self.turn,
+            self.iterations(),
+            self.elapsed().as_millis(),
+            self.budget().as_millis()
+        );
+    }
+}
+impl Default for TurnClock {
+    fn default() -> TurnClock {
+        TurnClock::new()
+    }
+}
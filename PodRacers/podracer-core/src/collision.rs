@@ -0,0 +1,112 @@
+use crate::constants::{MIN_IMPULSE, POD_RADIUS, SHIELD_MASS};
+use crate::geometry::ray_circle_intersection;
+use crate::pod::PodInfo;
+use crate::vector::{Scalar, Vector};
+
+// #########################################
+// ##              Collision              ##
+// #########################################
+// Pod against pod contact, resolved as the official elastic bounce.
+
+pub fn mass(pod: &PodInfo) -> Scalar {
+    if pod.shield {
+        SHIELD_MASS
+    } else {
+        1.0
+    }
+}
+
+// Time within [0, limit] at which both pods touch, if they do while moving
+pub fn collision_time(a: &PodInfo, b: &PodInfo, limit: Scalar) -> Option<Scalar> {
+    let relative_position = a.position - b.position;
+    let relative_speed = a.speed - b.speed;
+    let contact_distance = 2.0 * POD_RADIUS;
+
+    let approaching = relative_position.dot_product(&relative_speed) < 0.0;
+    let distance_sq = relative_position.dot_product(&relative_position);
+    if distance_sq <= contact_distance.powf(2.0) {
+        // Already touching: only counts while still closing in
+        return if approaching { Some(0.0) } else { None };
+    }
+
+    if !approaching {
+        return None;
+    }
+    // Relative to b, a runs into the circle where they touch
+    ray_circle_intersection(&relative_position, &relative_speed, &Vector::zero(), contact_distance)
+        .filter(|&time| time <= limit)
+}
+
+// Elastic bounce between two touching pods. The impulse is applied twice, the
+// second time raised to at least MIN_IMPULSE, as the referee does.
+pub fn bounce(a: &mut PodInfo, b: &mut PodInfo) {
+    let mass_a = mass(a);
+    let mass_b = mass(b);
+    let mass_coefficient = (mass_a + mass_b) / (mass_a * mass_b);
+
+    let normal = a.position - b.position;
+    let normal_sq = normal.dot_product(&normal);
+    if normal_sq == 0.0 {
+        return;
+    }
+    let relative_speed = a.speed - b.speed;
+    let product = normal.dot_product(&relative_speed);
+
+    let force = normal * (product / (normal_sq * mass_coefficient));
+    a.speed = a.speed - force * (1.0 / mass_a);
+    b.speed = b.speed + force * (1.0 / mass_b);
+
+    let impulse = force.module();
+    let force = if impulse < MIN_IMPULSE && impulse > 0.0 {
+        force * (MIN_IMPULSE / impulse)
+    } else {
+        force
+    };
+    a.speed = a.speed - force * (1.0 / mass_a);
+    b.speed = b.speed + force * (1.0 / mass_b);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::angle::Degrees;
+
+    fn pod(x: Scalar, speed_x: Scalar) -> PodInfo {
+        PodInfo::new(x, 0.0, speed_x, 0.0, Degrees(0.0), 0)
+    }
+
+    fn assert_speed(pod: &PodInfo, speed_x: Scalar) {
+        assert!((pod.speed.x - speed_x).abs() < 1e-3 && pod.speed.y == 0.0, "{} instead of {}", pod.speed, speed_x);
+    }
+
+    #[test]
+    fn times_contact_of_closing_pods_only() {
+        // 1000 apart, closing at 200 a turn: touching 800 apart, after a turn
+        assert_eq!(collision_time(&pod(0.0, 100.0), &pod(1000.0, -100.0), 1.0), Some(1.0));
+        assert_eq!(collision_time(&pod(0.0, 100.0), &pod(1000.0, -100.0), 0.5), None);
+        assert_eq!(collision_time(&pod(0.0, -100.0), &pod(1000.0, 100.0), 1.0), None);
+        assert_eq!(collision_time(&pod(0.0, 100.0), &pod(700.0, 0.0), 1.0), Some(0.0));
+        assert_eq!(collision_time(&pod(0.0, -100.0), &pod(700.0, 0.0), 1.0), None);
+    }
+
+    #[test]
+    fn raises_a_weak_head_on_impulse_to_the_minimum() {
+        // The impulse of 100 stops both pods, then 120 sends them back apart
+        let mut a = pod(0.0, 100.0);
+        let mut b = pod(800.0, -100.0);
+        bounce(&mut a, &mut b);
+        assert_speed(&a, -120.0);
+        assert_speed(&b, 120.0);
+    }
+
+    #[test]
+    fn shielded_pods_weigh_ten_times_more() {
+        // Masses 10 and 1: an impulse of 5000/11, applied twice
+        let mut a = pod(0.0, 0.0);
+        a.shield = true;
+        let mut b = pod(800.0, -500.0);
+        bounce(&mut a, &mut b);
+        assert_speed(&a, -1000.0 / 11.0);
+        assert_speed(&b, 4500.0 / 11.0);
+    }
+}
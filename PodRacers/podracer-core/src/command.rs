@@ -0,0 +1,144 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::constants::MAX_THRUST;
+use crate::position::Position;
+
+// #########################################
+// ##               Command               ##
+// #########################################
+// Longest message shown next to a pod, longer ones are cut
+pub const MAX_MESSAGE_LENGTH: usize = 40;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Action {
+    Thrust(u8),
+    Boost,
+    Shield,
+}
+
+// One output line: "x y thrust", "x y BOOST" or "x y SHIELD", then an optional
+// message shown next to the pod
+#[derive(Clone, Debug, PartialEq)]
+pub struct Command {
+    pub target: Position,
+    pub action: Action,
+    pub message: Option<String>,
+}
+impl Command {
+    pub fn new(target: Position, action: Action) -> Command {
+        Command {
+            target,
+            action,
+            message: None,
+        }
+    }
+    pub fn with_message(mut self, message: &str) -> Command {
+        self.message = Some(message.to_string());
+        self
+    }
+}
+impl fmt::Display for Command {
+    // Always a valid line: thrust within range, message on a single line and
+    // cut to length
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.action {
+            Action::Thrust(thrust) => write!(f, "{} {}", self.target, thrust.min(MAX_THRUST))?,
+            Action::Boost => write!(f, "{} BOOST", self.target)?,
+            Action::Shield => write!(f, "{} SHIELD", self.target)?,
+        }
+
+        let message = self
+            .message
+            .iter()
+            .flat_map(|message| message.split_whitespace())
+            .collect::<Vec<_>>()
+            .join(" ");
+        if !message.is_empty() {
+            write!(f, " {}", message.chars().take(MAX_MESSAGE_LENGTH).collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+impl FromStr for Command {
+    type Err = String;
+
+    // Anything after the action is the message
+    fn from_str(line: &str) -> Result<Command, String> {
+        let line = line.trim();
+        let mut rest = line;
+        let mut fields = Vec::with_capacity(3);
+        while fields.len() < 3 && !rest.is_empty() {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            fields.push(&rest[..end]);
+            rest = rest[end..].trim_start();
+        }
+        if fields.len() < 3 {
+            return Err(format!("expected \"x y thrust\", got \"{}\"", line));
+        }
+
+        let coordinate = |field: &str| {
+            field
+                .parse::<i32>()
+                .map_err(|_| format!("invalid coordinate \"{}\" in \"{}\"", field, line))
+        };
+        let target = Position::new(coordinate(fields[0])?, coordinate(fields[1])?);
+        let action = match fields[2] {
+            "BOOST" => Action::Boost,
+            "SHIELD" => Action::Shield,
+            thrust => match thrust.parse::<u8>() {
+                Ok(thrust) if thrust <= MAX_THRUST => Action::Thrust(thrust),
+                _ => return Err(format!("invalid thrust \"{}\" in \"{}\"", thrust, line)),
+            },
+        };
+        let message = if rest.is_empty() { None } else { Some(rest.to_string()) };
+
+        Ok(Command { target, action, message })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(action: Action) -> Command {
+        Command::new(Position::new(8000, -1500), action)
+    }
+
+    #[test]
+    fn clamps_the_thrust_written() {
+        assert_eq!(command(Action::Thrust(250)).to_string(), "8000 -1500 100");
+        assert_eq!(command(Action::Thrust(0)).to_string(), "8000 -1500 0");
+    }
+
+    #[test]
+    fn writes_messages_on_one_line_cut_to_length() {
+        let message = command(Action::Thrust(50)).with_message("  go\tgo\n\ngo  ");
+        assert_eq!(message.to_string(), "8000 -1500 50 go go go");
+
+        let long = "x".repeat(MAX_MESSAGE_LENGTH + 10);
+        let line = command(Action::Thrust(50)).with_message(&long).to_string();
+        assert_eq!(line, format!("8000 -1500 50 {}", "x".repeat(MAX_MESSAGE_LENGTH)));
+
+        assert_eq!(command(Action::Thrust(50)).with_message(" \n ").to_string(), "8000 -1500 50");
+    }
+
+    #[test]
+    fn reads_back_what_it_writes() {
+        for action in [Action::Thrust(0), Action::Thrust(MAX_THRUST), Action::Boost, Action::Shield] {
+            let written = command(action).with_message("hunter 1");
+            assert_eq!(written.to_string().parse::<Command>(), Ok(written));
+        }
+        assert_eq!("8000 -1500 BOOST".parse::<Command>(), Ok(command(Action::Boost)));
+    }
+
+    #[test]
+    fn rejects_invalid_lines() {
+        assert!("8000 -1500 101".parse::<Command>().is_err());
+        assert!("8000 -1500 -1".parse::<Command>().is_err());
+        assert!("8000.5 -1500 100".parse::<Command>().is_err());
+        assert!("8000 -1500.0 100".parse::<Command>().is_err());
+        assert!("8000 -1500 boost".parse::<Command>().is_err());
+        assert!("8000 -1500".parse::<Command>().is_err());
+    }
+}
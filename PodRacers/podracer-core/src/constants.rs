@@ -0,0 +1,29 @@
+use crate::angle::Degrees;
+use crate::vector::Scalar;
+
+// #########################################
+// ##              Constants              ##
+// #########################################
+pub const MAP_WIDTH: Scalar = 16000.0;
+pub const MAP_HEIGHT: Scalar = 9000.0;
+
+pub const POD_RADIUS: Scalar = 400.0;
+pub const CHECKPOINT_RADIUS: Scalar = 600.0;
+
+pub const BOOSTS_PER_GAME: i32 = 1;
+// The single-pod protocol never gives the lap count, those races run 3 laps
+pub const SINGLE_POD_LAPS: usize = 3;
+
+pub fn map_diagonal() -> Scalar {
+    (MAP_WIDTH.powf(2.0) + MAP_HEIGHT.powf(2.0)).sqrt()
+}
+
+pub const MAX_ROTATION: Degrees = Degrees(18.0);
+pub const MAX_THRUST: u8 = 100;
+pub const BOOST_THRUST: Scalar = 650.0;
+pub const FRICTION: Scalar = 0.85;
+pub const TIMEOUT_TURNS: i32 = 100;
+
+pub const SHIELD_MASS: Scalar = 10.0;
+pub const SHIELD_COOLDOWN_TURNS: i32 = 3;
+pub const MIN_IMPULSE: Scalar = 120.0;
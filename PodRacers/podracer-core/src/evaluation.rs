@@ -0,0 +1,32 @@
+use crate::pod::PodInfo;
+use crate::roles::{leading_pod, Role};
+use crate::simulation::GameState;
+use crate::track::Track;
+use crate::vector::Scalar;
+
+// #########################################
+// ##              Evaluation             ##
+// #########################################
+// Score of a searched state, the higher the better for us.
+
+// Weight of the hunter's blocking against the runner's progress
+const HUNTER_WEIGHT: Scalar = 0.5;
+
+// Race progress of the runners, plus how well the hunters hold the leading enemy back
+pub fn evaluate(track: &Track, state: &GameState<4>, roles: &[Role; 2]) -> Scalar {
+    let enemy_leader = 2 + leading_pod(track, &state.pods[2..]);
+    roles
+        .iter()
+        .zip(&state.pods)
+        .map(|(role, pod)| match role {
+            Role::Runner => -track.remaining_distance(pod),
+            Role::Hunter => HUNTER_WEIGHT * hunt_score(track, pod, &state.pods[enemy_leader]),
+        })
+        .sum()
+}
+
+// Race left to the enemy, minus how far the hunter is from the checkpoint it heads to
+fn hunt_score(track: &Track, hunter: &PodInfo, enemy: &PodInfo) -> Scalar {
+    let checkpoint = &track.checkpoints[enemy.next_checkpoint];
+    track.remaining_distance(enemy) - checkpoint.distance(&hunter.position)
+}
@@ -0,0 +1,210 @@
+use crate::clock::TurnClock;
+use crate::command::{Action, Command};
+use crate::constants::{MAX_ROTATION, MAX_THRUST};
+use crate::evaluation::evaluate;
+use crate::moves::{rush_commands, turn_command, Move};
+use crate::opponent::OpponentModel;
+use crate::planner::Planner;
+use crate::rng::Rng;
+use crate::roles::{Role, RoleAssigner};
+use crate::simulation::{simulate, GameState};
+use crate::track::Track;
+use crate::vector::Scalar;
+
+// #########################################
+// ##           Genetic planner           ##
+// #########################################
+// Evolves a few turns of moves for both our pods at once, scoring every
+// candidate through the simulation.
+pub const GENOME_DEPTH: usize = 5;
+const POPULATION_SIZE: usize = 12;
+const ELITE_SIZE: usize = POPULATION_SIZE / 2;
+const MUTATION_RATE: Scalar = 0.3;
+const BOOST_PROBABILITY: Scalar = 0.05;
+const SHIELD_PROBABILITY: Scalar = 0.05;
+
+// A gene is one turn of one pod
+pub type Gene = Move;
+
+#[derive(Clone, Debug)]
+pub struct Genome {
+    pub genes: [[Gene; GENOME_DEPTH]; 2],
+    pub score: Scalar,
+}
+
+pub struct GeneticPlanner {
+    track: Track,
+    rng: Rng,
+    // Best genome of last turn, shifted by one turn to seed the next search
+    best: Option<Genome>,
+    role_assigner: RoleAssigner,
+    // What each of our pods is scored on this turn
+    roles: [Role; 2],
+}
+impl GeneticPlanner {
+    pub fn new(track: Track, seed: u64) -> GeneticPlanner {
+        GeneticPlanner {
+            track,
+            rng: Rng::new(seed),
+            best: None,
+            role_assigner: RoleAssigner::new(),
+            roles: [Role::Runner; 2],
+        }
+    }
+
+    // +--------------------------+
+    // |     Genetic operators    |
+    // +--------------------------+
+    fn random_gene(&mut self) -> Gene {
+        let rotation = MAX_ROTATION * (self.rng.next_float() * 2.0 - 1.0);
+        let roll = self.rng.next_float();
+        let action = if roll < BOOST_PROBABILITY {
+            Action::Boost
+        } else if roll < BOOST_PROBABILITY + SHIELD_PROBABILITY {
+            Action::Shield
+        } else if self.rng.next_float() < 0.5 {
+            Action::Thrust(MAX_THRUST)
+        } else {
+            Action::Thrust(self.rng.range(0, MAX_THRUST as i32) as u8)
+        };
+        Gene { rotation, action }
+    }
+
+    fn random_genome(&mut self) -> Genome {
+        let mut genes = [[self.random_gene(); GENOME_DEPTH]; 2];
+        for pod_genes in &mut genes {
+            for gene in pod_genes.iter_mut() {
+                *gene = self.random_gene();
+            }
+        }
+        Genome { genes, score: 0.0 }
+    }
+
+    // Drops the turn already played and appends a random one
+    fn shifted(&mut self, genome: &Genome) -> Genome {
+        let mut genes = genome.genes;
+        for pod_genes in &mut genes {
+            pod_genes.rotate_left(1);
+            pod_genes[GENOME_DEPTH - 1] = self.random_gene();
+        }
+        Genome { genes, score: 0.0 }
+    }
+
+    fn crossover(&mut self, mother: &Genome, father: &Genome) -> Genome {
+        let mut genes = mother.genes;
+        for (pod, pod_genes) in genes.iter_mut().enumerate() {
+            for (turn, gene) in pod_genes.iter_mut().enumerate() {
+                if self.rng.next_float() < 0.5 {
+                    *gene = father.genes[pod][turn];
+                }
+            }
+        }
+        Genome { genes, score: 0.0 }
+    }
+
+    fn mutate(&mut self, genome: &mut Genome) {
+        for pod_genes in &mut genome.genes {
+            for gene in pod_genes.iter_mut() {
+                if self.rng.next_float() >= MUTATION_RATE {
+                    continue;
+                }
+                if self.rng.next_float() < 0.5 {
+                    let nudge = MAX_ROTATION * (self.rng.next_float() * 2.0 - 1.0) / 2.0;
+                    gene.rotation = (gene.rotation + nudge).clamp(-MAX_ROTATION, MAX_ROTATION);
+                } else {
+                    gene.action = self.random_gene().action;
+                }
+            }
+        }
+    }
+}
+
+impl Planner for GeneticPlanner {
+    // Evolves until the turn clock runs out, and returns the first move of the best genome
+    fn plan(&mut self, state: &GameState<4>, _opponents: &OpponentModel, clock: &TurnClock) -> [Command; 2] {
+        self.roles = self.role_assigner.assign(&self.track, &state.pods[..2]);
+
+        let mut population = Vec::with_capacity(POPULATION_SIZE);
+        if let Some(previous) = self.best.take() {
+            population.push(self.shifted(&previous));
+        }
+        while population.len() < POPULATION_SIZE {
+            let genome = self.random_genome();
+            population.push(genome);
+        }
+        for genome in &mut population {
+            genome.score = evaluate_genome(&self.track, state, &self.roles, genome);
+        }
+
+        loop {
+            population.sort_by(|a, b| b.score.total_cmp(&a.score));
+            clock.tick();
+            if clock.is_over() {
+                break;
+            }
+
+            for index in ELITE_SIZE..POPULATION_SIZE {
+                let mother = self.rng.range(0, ELITE_SIZE as i32 - 1) as usize;
+                let father = self.rng.range(0, ELITE_SIZE as i32 - 1) as usize;
+                let mut child = self.crossover(&population[mother], &population[father]);
+                self.mutate(&mut child);
+                child.score = evaluate_genome(&self.track, state, &self.roles, &child);
+                population[index] = child;
+            }
+        }
+
+        let best = population.swap_remove(0);
+        let commands = [
+            turn_command(&self.track, state, 0, &best.genes[0][0]),
+            turn_command(&self.track, state, 1, &best.genes[1][0]),
+        ];
+        self.best = Some(best);
+        commands
+    }
+}
+
+// +--------------------------+
+// |        Evaluation        |
+// +--------------------------+
+// Plays the genome out against rushing enemies and scores where it leads
+fn evaluate_genome(track: &Track, state: &GameState<4>, roles: &[Role; 2], genome: &Genome) -> Scalar {
+    let mut state = state.clone();
+    for turn in 0..GENOME_DEPTH {
+        let mut commands = rush_commands(track, &state);
+        commands[0] = turn_command(track, &state, 0, &genome.genes[0][turn]);
+        commands[1] = turn_command(track, &state, 1, &genome.genes[1][turn]);
+        state = simulate(track, &state, &commands);
+    }
+    evaluate(track, &state, roles)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::angle::Degrees;
+    use crate::clock::{FIRST_TURN_LIMIT, TURN_LIMIT};
+    use crate::pod::PodInfo;
+    use crate::vector::Vector;
+
+    #[test]
+    fn heads_for_the_checkpoint_on_the_first_turn() {
+        // Checkpoint 1 due west, and the input's heading of -1 pointing east
+        let track = Track::new(3, vec![Vector::new(12000.0, 4000.0), Vector::new(2000.0, 4500.0)]);
+        let pod = |y| PodInfo::new(12000.0, y, 0.0, 0.0, Degrees(-1.0), 1);
+        let state = GameState {
+            pods: [pod(3500.0), pod(4500.0), pod(3000.0), pod(5000.0)],
+            turn: 0,
+        };
+        let search_time = Duration::from_millis(20);
+        let mut clock = TurnClock::with_margins(FIRST_TURN_LIMIT - search_time, TURN_LIMIT - search_time);
+        clock.start_turn();
+
+        let commands = GeneticPlanner::new(track, 0).plan(&state, &OpponentModel::new(), &clock);
+        for command in &commands {
+            assert!(command.target.x < 10000, "{}", command);
+            assert_ne!(command.action, Action::Shield, "{}", command);
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use crate::vector::{Scalar, Vector};
+
+// #########################################
+// ##              Geometry               ##
+// #########################################
+// Intersections and distances between lines, segments and circles. Every
+// degenerate case (parallel lines, null directions, misses) gives None rather
+// than a NaN or infinite point.
+
+// Point where the line through `a` along `a_direction` crosses the one through
+// `b` along `b_direction`. None when they are parallel.
+pub fn line_intersection(a: &Vector, a_direction: &Vector, b: &Vector, b_direction: &Vector) -> Option<Vector> {
+    // a + t*a_direction = b + u*b_direction, crossed with b_direction
+    let denominator = a_direction.determinant(b_direction);
+    if denominator == 0.0 {
+        return None;
+    }
+    let t = (*b - *a).determinant(b_direction) / denominator;
+    Some(*a + *a_direction * t)
+}
+
+// Smallest t >= 0 for which `origin + t*direction` is inside the circle: 0
+// when the origin already is, else where the ray enters it
+pub fn ray_circle_intersection(origin: &Vector, direction: &Vector, center: &Vector, radius: Scalar) -> Option<Scalar> {
+    // |relative + t*direction| = radius
+    let relative = *origin - *center;
+    let a = direction.dot_product(direction);
+    let b = 2.0 * relative.dot_product(direction);
+    let c = relative.dot_product(&relative) - radius.powf(2.0);
+
+    if c <= 0.0 {
+        return Some(0.0);
+    }
+    if a == 0.0 {
+        return None;
+    }
+    let discriminant = b.powf(2.0) - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    let t = (-b - discriminant.sqrt()) / (2.0 * a);
+    if t >= 0.0 {
+        Some(t)
+    } else {
+        None
+    }
+}
+
+// Same as the ray, for the segment from `start` to `end`: t within [0, 1]
+pub fn segment_circle_intersection(start: &Vector, end: &Vector, center: &Vector, radius: Scalar) -> Option<Scalar> {
+    ray_circle_intersection(start, &(*end - *start), center, radius).filter(|&t| t <= 1.0)
+}
+
+pub fn point_segment_distance(point: &Vector, start: &Vector, end: &Vector) -> Scalar {
+    let segment = *end - *start;
+    let length_sq = segment.dot_product(&segment);
+    if length_sq == 0.0 {
+        return point.distance(start);
+    }
+    let t = ((*point - *start).dot_product(&segment) / length_sq).clamp(0.0, 1.0);
+    point.distance(&(*start + segment * t))
+}
+
+// +--------------------------+
+// |      Moving circles      |
+// +--------------------------+
+#[derive(Clone, Debug)]
+pub struct MovingCircle {
+    pub position: Vector,
+    pub speed: Vector,
+    pub radius: Scalar,
+}
+
+// When two moving circles get closest, from now on, and the gap between their
+// borders then, negative when they overlap
+#[derive(Clone, Debug, PartialEq)]
+pub struct Approach {
+    pub time: Scalar,
+    pub gap: Scalar,
+}
+
+pub fn closest_approach(a: &MovingCircle, b: &MovingCircle) -> Approach {
+    let relative_position = a.position - b.position;
+    let relative_speed = a.speed - b.speed;
+    let speed_sq = relative_speed.dot_product(&relative_speed);
+    // Circles moving apart, or together, are closest right away
+    let time = if speed_sq == 0.0 {
+        0.0
+    } else {
+        (-relative_position.dot_product(&relative_speed) / speed_sq).max(0.0)
+    };
+    let distance = (relative_position + relative_speed * time).module();
+    Approach {
+        time,
+        gap: distance - a.radius - b.radius,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crosses_lines_unless_parallel() {
+        let origin = Vector::zero();
+        let crossing = line_intersection(&origin, &Vector::new(1.0, 1.0), &Vector::new(4.0, 0.0), &Vector::new(0.0, 2.0));
+        assert_eq!(crossing, Some(Vector::new(4.0, 4.0)));
+
+        let parallel = line_intersection(&origin, &Vector::new(1.0, 2.0), &Vector::new(3.0, 0.0), &Vector::new(2.0, 4.0));
+        assert_eq!(parallel, None);
+        assert_eq!(line_intersection(&origin, &Vector::zero(), &origin, &Vector::new(1.0, 0.0)), None);
+    }
+
+    #[test]
+    fn enters_circles_ahead_only() {
+        let center = Vector::new(10.0, 0.0);
+        let origin = Vector::zero();
+        assert_eq!(ray_circle_intersection(&origin, &Vector::new(2.0, 0.0), &center, 4.0), Some(3.0));
+        assert_eq!(ray_circle_intersection(&origin, &Vector::new(-1.0, 0.0), &center, 4.0), None);
+        assert_eq!(ray_circle_intersection(&origin, &Vector::new(0.0, 1.0), &center, 4.0), None);
+        assert_eq!(ray_circle_intersection(&origin, &Vector::zero(), &center, 4.0), None);
+        assert_eq!(ray_circle_intersection(&center, &Vector::zero(), &center, 4.0), Some(0.0));
+
+        assert_eq!(segment_circle_intersection(&origin, &Vector::new(8.0, 0.0), &center, 4.0), Some(0.75));
+        assert_eq!(segment_circle_intersection(&origin, &Vector::new(5.0, 0.0), &center, 4.0), None);
+    }
+
+    #[test]
+    fn measures_distance_to_segments() {
+        let start = Vector::new(0.0, 0.0);
+        let end = Vector::new(10.0, 0.0);
+        assert_eq!(point_segment_distance(&Vector::new(5.0, 3.0), &start, &end), 3.0);
+        assert_eq!(point_segment_distance(&Vector::new(13.0, 4.0), &start, &end), 5.0);
+        assert_eq!(point_segment_distance(&Vector::new(3.0, 4.0), &start, &start), 5.0);
+    }
+
+    #[test]
+    fn finds_closest_approach_of_moving_circles() {
+        let circle = |x, y, speed_x, speed_y| MovingCircle {
+            position: Vector::new(x, y),
+            speed: Vector::new(speed_x, speed_y),
+            radius: 1.0,
+        };
+
+        let crossing = closest_approach(&circle(0.0, 0.0, 1.0, 0.0), &circle(10.0, 3.0, -1.0, 0.0));
+        assert_eq!(crossing, Approach { time: 5.0, gap: 1.0 });
+
+        let leaving = closest_approach(&circle(0.0, 0.0, -1.0, 0.0), &circle(10.0, 0.0, 1.0, 0.0));
+        assert_eq!(leaving, Approach { time: 0.0, gap: 8.0 });
+
+        let together = closest_approach(&circle(0.0, 0.0, 1.0, 1.0), &circle(1.0, 0.0, 1.0, 1.0));
+        assert_eq!(together, Approach { time: 0.0, gap: -1.0 });
+    }
+}
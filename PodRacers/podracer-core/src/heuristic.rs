@@ -0,0 +1,134 @@
+use crate::clock::TurnClock;
+use crate::command::{Action, Command};
+use crate::constants::{CHECKPOINT_RADIUS, FRICTION};
+use crate::hunter::hunter_command;
+use crate::opponent::OpponentModel;
+use crate::planner::Planner;
+use crate::pod::PodInfo;
+use crate::roles::{Role, RoleAssigner};
+use crate::shield::should_shield;
+use crate::simulation::GameState;
+use crate::steering::{steer, turns_to_face};
+use crate::target::gate_target;
+use crate::throttle::best_thrust;
+use crate::track::Track;
+use crate::vector::{Scalar, Vector};
+
+// #########################################
+// ##          Heuristic planner          ##
+// #########################################
+// Hand-written steering, one pod at a time: the runner follows the racing
+// line, the hunter blocks the leading enemy.
+
+// Shortest straight worth the BOOST
+const RUNNER_BOOST_DISTANCE: Scalar = CHECKPOINT_RADIUS * 5.0;
+
+pub struct HeuristicPlanner {
+    track: Track,
+    role_assigner: RoleAssigner,
+}
+impl HeuristicPlanner {
+    pub fn new(track: Track) -> HeuristicPlanner {
+        HeuristicPlanner {
+            track,
+            role_assigner: RoleAssigner::new(),
+        }
+    }
+
+    fn runner_command(&self, player_info: &PodInfo, enemy_pods: &[PodInfo], first_turn: bool) -> Command {
+        let track = &self.track;
+        let checkpoint = &track.checkpoints[player_info.next_checkpoint];
+        let following = track.checkpoints.next(player_info.next_checkpoint, 1);
+        let final_gate = track.is_final_checkpoint(player_info.checkpoints_passed);
+
+        let command = race_command(player_info, checkpoint, Some(following), final_gate, RUNNER_BOOST_DISTANCE, first_turn);
+
+        // +--------------------------+
+        // |     SHIELD calculation   |
+        // +--------------------------+
+        // If an enemy is about to hit us hard, take the hit with the shield up
+        if should_shield(player_info, enemy_pods, checkpoint) {
+            Command::new(command.target, Action::Shield)
+        } else {
+            command
+        }
+    }
+}
+impl Planner for HeuristicPlanner {
+    fn plan(&mut self, state: &GameState<4>, opponents: &OpponentModel, _clock: &TurnClock) -> [Command; 2] {
+        // +--------------------------+
+        // |     Roles assignment     |
+        // +--------------------------+
+        // The pod ahead races, the other one hunts the leading enemy
+        let roles = self.role_assigner.assign(&self.track, &state.pods[..2]);
+        let leader_index = opponents.leader(&self.track);
+        let enemy_leader = &opponents.pods[leader_index];
+        let leader_arrival = opponents.arrival_turns(&self.track, leader_index, 0);
+
+        [0, 1].map(|index| {
+            let player_info = &state.pods[index];
+            // eprintln!("Pod {}:", index);
+            // eprintln!("player_info: {}", player_info);
+            match roles[index] {
+                Role::Hunter => hunter_command(&self.track, player_info, enemy_leader, leader_arrival),
+                Role::Runner => self.runner_command(player_info, &state.pods[2..], state.turn == 0),
+            }
+        })
+    }
+}
+
+// +--------------------------+
+// |         Runner           |
+// +--------------------------+
+// Racing line of a pod, shared by both bots. `following` is the checkpoint
+// after `checkpoint` when known, `final_gate` whether `checkpoint` is the
+// finish line. BOOST goes on straights longer than `boost_distance`. Rotation
+// is free on the first turn, steering only applies afterwards.
+pub fn race_command(
+    pod: &PodInfo,
+    checkpoint: &Vector,
+    following: Option<&Vector>,
+    final_gate: bool,
+    boost_distance: Scalar,
+    first_turn: bool,
+) -> Command {
+    // +--------------------------+
+    // |   Initial calculations   |
+    // +--------------------------+
+    let position = &pod.position;
+    let checkpoint_dist = position.distance(checkpoint);
+    let turns_to_checkpoint = turns_to_face(position, pod.angle, checkpoint);
+    // Nothing to save speed for after the finish line
+    let following = if final_gate { None } else { following };
+
+    // +--------------------------+
+    // |     Target calculation   |
+    // +--------------------------+
+    // Planning for the following gate, unless this is the finish line. The
+    // drift is judged on the last move, before friction.
+    let drift = pod.speed * (1.0 / FRICTION);
+    let target = gate_target(position, &drift, checkpoint, following);
+    // Best heading within reach this turn
+    let target = if first_turn {
+        target
+    } else {
+        steer(position, pod.angle, &drift, &Vector::from(&target)).target.to_position()
+    };
+
+    // +--------------------------+
+    // |     Thrust calculation   |
+    // +--------------------------+
+    // Any thrust from 0 to 100, whichever passes this gate and the next one soonest
+    let thrust = best_thrust(pod, &Vector::from(&target), checkpoint, following, first_turn);
+
+    // +--------------------------+
+    // |      BOOS calculation    |
+    // +--------------------------+
+    // If we can still use the BOOST and we have distant enough to the next checkpoint, use it.
+    // Only once facing it: the BOOST pushes along the current heading, even on the final gate.
+    let can_boost = pod.remaining_boosts > 0;
+    let use_boost = can_boost && (final_gate || (checkpoint_dist > boost_distance)) && (turns_to_checkpoint <= 1);
+
+    let action = if use_boost { Action::Boost } else { Action::Thrust(thrust) };
+    Command::new(target, action)
+}
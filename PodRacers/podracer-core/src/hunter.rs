@@ -0,0 +1,126 @@
+use crate::collision::collision_time;
+use crate::command::{Action, Command};
+use crate::constants::{CHECKPOINT_RADIUS, MAX_THRUST};
+use crate::pod::PodInfo;
+use crate::prediction::{interception, predict_trajectory, PREDICTION_TURNS};
+use crate::track::Track;
+use crate::vector::Scalar;
+
+// #########################################
+// ##               Hunter                ##
+// #########################################
+// Steering of the pod that blocks instead of racing: it waits in front of the
+// enemy's next checkpoint when it can get there first, chases the enemy
+// otherwise, and raises the shield on contact.
+
+// Distance from the checkpoint, on the enemy's side, where the hunter waits
+const PARKING_DISTANCE: Scalar = 1500.0;
+// Distance from the parking spot over which the hunter brakes
+const BRAKING_DISTANCE: Scalar = 2000.0;
+// Speed the hunter is expected to keep on its way to the parking spot
+const CRUISE_SPEED: Scalar = 500.0;
+
+// `enemy_arrival_turns` is when the enemy is expected at its next checkpoint
+pub fn hunter_command(track: &Track, hunter: &PodInfo, enemy: &PodInfo, enemy_arrival_turns: Scalar) -> Command {
+    // Contact coming: make the enemy bounce off
+    if hunter.shield_cooldown == 0 && collision_time(hunter, enemy, 1.0).is_some() {
+        return Command::new(enemy.position.to_position(), Action::Shield);
+    }
+
+    let checkpoint = &track.checkpoints[enemy.next_checkpoint];
+    let hunter_arrival_turns = checkpoint.distance(&hunter.position) / CRUISE_SPEED;
+
+    if hunter_arrival_turns < enemy_arrival_turns {
+        // +--------------------------+
+        // |         Parking          |
+        // +--------------------------+
+        let from_checkpoint = enemy.position - *checkpoint;
+        let parking = if from_checkpoint.is_zero() {
+            *checkpoint
+        } else {
+            *checkpoint + from_checkpoint.normalize_or_zero() * PARKING_DISTANCE
+        };
+        let parking_distance = parking.distance(&hunter.position);
+
+        if parking_distance < CHECKPOINT_RADIUS {
+            // In place: face the enemy, ready to meet it
+            Command::new(enemy.position.to_position(), Action::Thrust(0))
+        } else {
+            let thrust = (MAX_THRUST as Scalar * parking_distance / BRAKING_DISTANCE).min(MAX_THRUST as Scalar);
+            Command::new(parking.to_position(), Action::Thrust(thrust as u8))
+        }
+    } else {
+        // +--------------------------+
+        // |         Chasing          |
+        // +--------------------------+
+        // Aim where the enemy will be when we can get there, or at least where it is going
+        let trajectory = predict_trajectory(track, enemy, PREDICTION_TURNS);
+        let intercept = match interception(track, hunter, &trajectory) {
+            Some(interception) => interception.point,
+            None => trajectory.last().cloned().unwrap_or(enemy.position),
+        };
+        Command::new(intercept.to_position(), Action::Thrust(MAX_THRUST))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::angle::Degrees;
+    use crate::position::Position;
+    use crate::vector::Vector;
+
+    fn track() -> Track {
+        Track::new(3, vec![Vector::new(10000.0, 5000.0), Vector::new(2000.0, 5000.0)])
+    }
+
+    // Heading east to checkpoint 0, 6000 away
+    fn enemy() -> PodInfo {
+        PodInfo::new(4000.0, 5000.0, 0.0, 0.0, Degrees(0.0), 0)
+    }
+
+    #[test]
+    fn shields_before_contact() {
+        let mut enemy = enemy();
+        enemy.speed = Vector::new(200.0, 0.0);
+        let mut hunter = PodInfo::new(4900.0, 5000.0, -200.0, 0.0, Degrees(180.0), 0);
+        let command = hunter_command(&track(), &hunter, &enemy, 10.0);
+        assert_eq!(command, Command::new(Position::new(4000, 5000), Action::Shield));
+
+        // Not while the shield is cooling down
+        hunter.shield_cooldown = 2;
+        assert_ne!(hunter_command(&track(), &hunter, &enemy, 10.0).action, Action::Shield);
+    }
+
+    #[test]
+    fn parks_in_front_of_the_checkpoint() {
+        let track = track();
+        // The spot is 1500 before the checkpoint on the enemy's side: (8500, 5000)
+        let hunter = PodInfo::new(8500.0, 6000.0, 0.0, 0.0, Degrees(90.0), 0);
+        let command = hunter_command(&track, &hunter, &enemy(), 10.0);
+        assert_eq!(command, Command::new(Position::new(8500, 5000), Action::Thrust(50)));
+
+        // In place, waiting for the enemy
+        let hunter = PodInfo::new(8700.0, 5200.0, 0.0, 0.0, Degrees(180.0), 0);
+        let command = hunter_command(&track, &hunter, &enemy(), 10.0);
+        assert_eq!(command, Command::new(Position::new(4000, 5000), Action::Thrust(0)));
+    }
+
+    #[test]
+    fn chases_an_enemy_it_cannot_beat_to_the_checkpoint() {
+        let track = track();
+        let trajectory = predict_trajectory(&track, &enemy(), PREDICTION_TURNS);
+
+        // Out of reach: aim where the enemy is going
+        let hunter = PodInfo::new(0.0, 5000.0, 0.0, 0.0, Degrees(0.0), 0);
+        let command = hunter_command(&track, &hunter, &enemy(), 10.0);
+        assert_eq!(command, Command::new(trajectory[PREDICTION_TURNS - 1].to_position(), Action::Thrust(MAX_THRUST)));
+
+        // Close behind: aim at the interception point
+        let hunter = PodInfo::new(4000.0, 3500.0, 0.0, 0.0, Degrees(90.0), 0);
+        let meeting = interception(&track, &hunter, &trajectory).unwrap();
+        let command = hunter_command(&track, &hunter, &enemy(), 10.0);
+        assert_eq!(command, Command::new(meeting.point.to_position(), Action::Thrust(MAX_THRUST)));
+        assert!(meeting.turn < PREDICTION_TURNS);
+    }
+}
@@ -0,0 +1,33 @@
+// Shared code for the Mad Pod Racing bots: geometry, game state and I/O.
+
+pub mod angle;
+pub mod checkpoint_ring;
+pub mod clock;
+pub mod collision;
+pub mod command;
+pub mod constants;
+pub mod evaluation;
+pub mod genetic;
+pub mod geometry;
+pub mod heuristic;
+pub mod hunter;
+pub mod minimax;
+pub mod monte_carlo;
+pub mod moves;
+pub mod opponent;
+pub mod physics;
+pub mod planner;
+pub mod pod;
+pub mod position;
+pub mod prediction;
+pub mod protocol;
+pub mod rng;
+pub mod roles;
+pub mod shield;
+pub mod simulation;
+pub mod steering;
+pub mod target;
+pub mod throttle;
+pub mod track;
+pub mod track_learner;
+pub mod vector;
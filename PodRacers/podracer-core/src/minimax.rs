@@ -0,0 +1,215 @@
+use crate::angle::Degrees;
+use crate::clock::TurnClock;
+use crate::command::{Action, Command};
+use crate::constants::{MAX_ROTATION, MAX_THRUST};
+use crate::evaluation::evaluate;
+use crate::moves::{legal_actions, rush_commands, turn_command, Move};
+use crate::opponent::OpponentModel;
+use crate::planner::Planner;
+use crate::roles::{Role, RoleAssigner};
+use crate::simulation::{simulate, GameState};
+use crate::track::Track;
+use crate::vector::Scalar;
+
+// #########################################
+// ##           Minimax planner           ##
+// #########################################
+// Paranoid search: every turn our two pods pick a joint move, then the two
+// enemy pods pick the reply that hurts us most, knowing ours. Searched with
+// alpha-beta over a coarse action set, deepening one turn at a time for as
+// long as the turn clock allows.
+pub const MAX_DEPTH: usize = 3;
+// Turns everybody rushes on after the searched ones, so the score sees where
+// the pods' momentum takes them
+const ROLLOUT_TURNS: usize = 2;
+
+// Straight first, so the likely best moves get searched first. On the first
+// turn, straight is at the next checkpoint.
+const ROTATIONS: [Degrees; 3] = [Degrees(0.0), Degrees(-MAX_ROTATION.0), MAX_ROTATION];
+// Enemies only steer at full thrust. Free to brake or shield, their worst
+// replies block whatever we do, and our pods end up parked behind shields.
+const ENEMY_ACTIONS: [Action; 1] = [Action::Thrust(MAX_THRUST)];
+
+pub struct MinimaxPlanner {
+    track: Track,
+    // Enemy reply that refuted the previous move, per depth left, tried first
+    killers: [usize; MAX_DEPTH + 1],
+    role_assigner: RoleAssigner,
+}
+impl MinimaxPlanner {
+    pub fn new(track: Track) -> MinimaxPlanner {
+        MinimaxPlanner {
+            track,
+            killers: [0; MAX_DEPTH + 1],
+            role_assigner: RoleAssigner::new(),
+        }
+    }
+}
+impl Planner for MinimaxPlanner {
+    // Deepens the search until the turn clock runs out, and returns the best
+    // move of the deepest completed search
+    fn plan(&mut self, state: &GameState<4>, _opponents: &OpponentModel, clock: &TurnClock) -> [Command; 2] {
+        let mut search = Search {
+            track: &self.track,
+            clock,
+            roles: self.role_assigner.assign(&self.track, &state.pods[..2]),
+            killers: &mut self.killers,
+        };
+        let mut root_moves = joint_moves(state, 0);
+        let mut best = root_moves[0];
+
+        for depth in 1..=MAX_DEPTH {
+            // Best moves of the previous depth first, for early cut-offs
+            let mut scored = Vec::with_capacity(root_moves.len());
+            let mut alpha = Scalar::MIN;
+            for ours in &root_moves {
+                match search.min_value(state, ours, depth, alpha, Scalar::MAX) {
+                    Some(value) => {
+                        alpha = alpha.max(value);
+                        scored.push((*ours, value));
+                    }
+                    None => break,
+                }
+            }
+            if scored.len() < root_moves.len() {
+                break;
+            }
+
+            scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+            best = scored[0].0;
+            root_moves = scored.into_iter().map(|(ours, _)| ours).collect();
+        }
+
+        [turn_command(&self.track, state, 0, &best[0]), turn_command(&self.track, state, 1, &best[1])]
+    }
+}
+
+// +--------------------------+
+// |         Alpha-beta       |
+// +--------------------------+
+// What one turn's search needs along the tree
+struct Search<'a> {
+    track: &'a Track,
+    clock: &'a TurnClock,
+    roles: [Role; 2],
+    killers: &'a mut [usize; MAX_DEPTH + 1],
+}
+impl Search<'_> {
+    // Best score our pods can secure in `depth` turns. None once out of time.
+    fn max_value(&mut self, state: &GameState<4>, depth: usize, mut alpha: Scalar, beta: Scalar) -> Option<Scalar> {
+        if depth == 0 {
+            return Some(rollout(self.track, state, &self.roles));
+        }
+
+        let mut best = Scalar::MIN;
+        for ours in joint_moves(state, 0) {
+            let value = self.min_value(state, &ours, depth, alpha, beta)?;
+            best = best.max(value);
+            alpha = alpha.max(value);
+            if alpha >= beta {
+                break;
+            }
+        }
+        Some(best)
+    }
+
+    // Score left to us once the enemies found their best reply to our move
+    fn min_value(&mut self, state: &GameState<4>, ours: &[Move; 2], depth: usize, alpha: Scalar, mut beta: Scalar) -> Option<Scalar> {
+        let replies = joint_moves(state, 2);
+        let killer = self.killers[depth].min(replies.len() - 1);
+        let order = std::iter::once(killer).chain((0..replies.len()).filter(|&index| index != killer));
+
+        let mut worst = Scalar::MAX;
+        for index in order {
+            self.clock.tick();
+            if self.clock.is_over() {
+                return None;
+            }
+
+            let theirs = &replies[index];
+            let commands = [
+                turn_command(self.track, state, 0, &ours[0]),
+                turn_command(self.track, state, 1, &ours[1]),
+                turn_command(self.track, state, 2, &theirs[0]),
+                turn_command(self.track, state, 3, &theirs[1]),
+            ];
+            let next = simulate(self.track, state, &commands);
+            let value = self.max_value(&next, depth - 1, alpha, beta)?;
+            worst = worst.min(value);
+            beta = beta.min(value);
+            if alpha >= beta {
+                self.killers[depth] = index;
+                break;
+            }
+        }
+        Some(worst)
+    }
+}
+
+// Every combination of moves for the pair of pods starting at `first`
+fn joint_moves(state: &GameState<4>, first: usize) -> Vec<[Move; 2]> {
+    let first_moves = pod_moves(state, first);
+    let second_moves = pod_moves(state, first + 1);
+    let mut moves = Vec::with_capacity(first_moves.len() * second_moves.len());
+    for first_move in &first_moves {
+        for second_move in &second_moves {
+            moves.push([*first_move, *second_move]);
+        }
+    }
+    moves
+}
+
+// Coarse moves of one pod
+fn pod_moves(state: &GameState<4>, index: usize) -> Vec<Move> {
+    let actions = if index >= 2 { ENEMY_ACTIONS.to_vec() } else { legal_actions(&state.pods[index]) };
+    let mut moves = Vec::with_capacity(ROTATIONS.len() * actions.len());
+    for &rotation in &ROTATIONS {
+        for &action in &actions {
+            moves.push(Move { rotation, action });
+        }
+    }
+    moves
+}
+
+// +--------------------------+
+// |        Evaluation        |
+// +--------------------------+
+// Lets every pod rush on for a few turns, then scores the state
+fn rollout(track: &Track, state: &GameState<4>, roles: &[Role; 2]) -> Scalar {
+    let mut state = state.clone();
+    for _ in 0..ROLLOUT_TURNS {
+        state = simulate(track, &state, &rush_commands(track, &state));
+    }
+    evaluate(track, &state, roles)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::angle::Degrees;
+    use crate::clock::{FIRST_TURN_LIMIT, TURN_LIMIT};
+    use crate::pod::PodInfo;
+    use crate::vector::Vector;
+
+    #[test]
+    fn heads_for_the_checkpoint_on_the_first_turn() {
+        // Checkpoint 1 due west, and the input's heading of -1 pointing east
+        let track = Track::new(3, vec![Vector::new(12000.0, 4000.0), Vector::new(2000.0, 4500.0)]);
+        let pod = |y| PodInfo::new(12000.0, y, 0.0, 0.0, Degrees(-1.0), 1);
+        let state = GameState {
+            pods: [pod(3500.0), pod(4500.0), pod(3000.0), pod(5000.0)],
+            turn: 0,
+        };
+        let search_time = Duration::from_millis(20);
+        let mut clock = TurnClock::with_margins(FIRST_TURN_LIMIT - search_time, TURN_LIMIT - search_time);
+        clock.start_turn();
+
+        let commands = MinimaxPlanner::new(track).plan(&state, &OpponentModel::new(), &clock);
+        for command in &commands {
+            assert!(command.target.x < 10000, "{}", command);
+            assert_ne!(command.action, Action::Shield, "{}", command);
+        }
+    }
+}
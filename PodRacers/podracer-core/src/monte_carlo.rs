@@ -0,0 +1,317 @@
+use std::collections::VecDeque;
+
+use crate::clock::TurnClock;
+use crate::command::{Action, Command};
+use crate::constants::{MAX_ROTATION, MAX_THRUST};
+use crate::evaluation::evaluate;
+use crate::moves::{legal_actions, move_command, rush_commands, Move};
+use crate::opponent::OpponentModel;
+use crate::planner::Planner;
+use crate::roles::{leading_pod, Role, RoleAssigner};
+use crate::simulation::{simulate, GameState};
+use crate::target::gate_target;
+use crate::track::Track;
+use crate::vector::Scalar;
+
+// #########################################
+// ##         Monte-Carlo planner         ##
+// #########################################
+// Monte-Carlo tree search with UCT selection over the output lines of our two
+// pods. Each tree level decides for one pod, so a turn spans two levels, and
+// the enemies are expected to rush to their next checkpoint. Leaves are played
+// out by everybody rushing, then scored by the shared evaluation.
+pub const SEARCH_TURNS: usize = 4;
+// Turns from the root at which playouts stop and get scored
+const HORIZON_TURNS: usize = 6;
+// UCT exploration, for scores normalized to [0, 1]
+const EXPLORATION: Scalar = 0.4;
+
+struct Node {
+    // State at the start of the turn being decided
+    state: GameState<4>,
+    // Turns played from the root
+    depth: usize,
+    // Pod 0's command, once chosen, while deciding for pod 1
+    first: Option<Command>,
+    // Commands the deciding pod can pick, children are expanded in that order
+    options: Vec<Command>,
+    children: Vec<usize>,
+    visits: Scalar,
+    total: Scalar,
+}
+impl Node {
+    fn new(track: &Track, state: GameState<4>, depth: usize, first: Option<Command>) -> Node {
+        let pod = if first.is_some() { 1 } else { 0 };
+        Node {
+            options: pod_options(track, &state, pod),
+            state,
+            depth,
+            first,
+            children: Vec::new(),
+            visits: 0.0,
+            total: 0.0,
+        }
+    }
+}
+
+pub struct MonteCarloPlanner {
+    track: Track,
+    // Tree of the current turn, the root at index 0
+    nodes: Vec<Node>,
+    // Node reached by the commands played last turn, to carry its subtree over
+    played: Option<usize>,
+    // Roles the tree was scored with, its scores are void once they change
+    searched_roles: [Role; 2],
+    // Score range seen this turn, to normalize the UCT exploitation term
+    low: Scalar,
+    high: Scalar,
+    role_assigner: RoleAssigner,
+    // What each of our pods is scored on this turn
+    roles: [Role; 2],
+}
+impl MonteCarloPlanner {
+    pub fn new(track: Track) -> MonteCarloPlanner {
+        MonteCarloPlanner {
+            track,
+            nodes: Vec::new(),
+            played: None,
+            searched_roles: [Role::Runner; 2],
+            low: 0.0,
+            high: 0.0,
+            role_assigner: RoleAssigner::new(),
+            roles: [Role::Runner; 2],
+        }
+    }
+
+    // +--------------------------+
+    // |        Tree search       |
+    // +--------------------------+
+    // Selection, expansion, playout and backpropagation, once
+    fn iterate(&mut self) {
+        let mut path = vec![0];
+        let mut index = 0;
+        while self.nodes[index].depth < SEARCH_TURNS {
+            let node = &self.nodes[index];
+            if node.children.len() < node.options.len() {
+                index = self.expand(index);
+                path.push(index);
+                break;
+            }
+            index = self.select(index);
+            path.push(index);
+        }
+
+        let score = playout(&self.track, &self.nodes[index], &self.roles);
+        self.low = self.low.min(score);
+        self.high = self.high.max(score);
+        for index in path {
+            self.nodes[index].visits += 1.0;
+            self.nodes[index].total += score;
+        }
+    }
+
+    fn select(&self, index: usize) -> usize {
+        let node = &self.nodes[index];
+        let log_visits = node.visits.ln();
+        let range = (self.high - self.low).max(1.0);
+        let uct = |child: usize| {
+            let child = &self.nodes[child];
+            let mean = (child.total / child.visits - self.low) / range;
+            mean + EXPLORATION * (log_visits / child.visits).sqrt()
+        };
+        node.children
+            .iter()
+            .copied()
+            .max_by(|&a, &b| uct(a).total_cmp(&uct(b)))
+            .unwrap()
+    }
+
+    // Adds the next unexplored option of the node as a child
+    fn expand(&mut self, index: usize) -> usize {
+        let track = &self.track;
+        let parent = &self.nodes[index];
+        let command = parent.options[parent.children.len()].clone();
+        let child = match &parent.first {
+            None => Node::new(track, parent.state.clone(), parent.depth, Some(command)),
+            Some(first) => {
+                let state = &parent.state;
+                let mut commands = rush_commands(track, state);
+                commands[0] = first.clone();
+                commands[1] = command;
+                Node::new(track, simulate(track, state, &commands), parent.depth + 1, None)
+            }
+        };
+
+        self.nodes.push(child);
+        let child = self.nodes.len() - 1;
+        self.nodes[index].children.push(child);
+        child
+    }
+
+    // Position, among the node's children, of the most visited one
+    fn most_visited(&self, index: usize) -> Option<usize> {
+        let children = &self.nodes[index].children;
+        (0..children.len()).max_by(|&a, &b| self.nodes[children[a]].visits.total_cmp(&self.nodes[children[b]].visits))
+    }
+
+    // +--------------------------+
+    // |        Tree reuse        |
+    // +--------------------------+
+    // Keeps only the subtree of the node, renumbered breadth first from 0
+    fn reroot(&mut self, index: usize) {
+        let mut old = std::mem::take(&mut self.nodes).into_iter().map(Some).collect::<Vec<_>>();
+        let mut queue = VecDeque::from([index]);
+        let mut next_index = 1;
+        while let Some(old_index) = queue.pop_front() {
+            let mut node = old[old_index].take().unwrap();
+            queue.extend(&node.children);
+            for child in &mut node.children {
+                *child = next_index;
+                next_index += 1;
+            }
+            node.depth -= 1;
+            self.nodes.push(node);
+        }
+    }
+}
+impl Planner for MonteCarloPlanner {
+    // Grows the tree until the turn clock runs out, and returns the most
+    // visited commands
+    fn plan(&mut self, state: &GameState<4>, _opponents: &OpponentModel, clock: &TurnClock) -> [Command; 2] {
+        self.roles = self.role_assigner.assign(&self.track, &state.pods[..2]);
+
+        let reused = self.played.take().filter(|&index| {
+            self.searched_roles == self.roles && same_pods(&self.nodes[index].state, state)
+        });
+        match reused {
+            Some(index) => self.reroot(index),
+            None => self.nodes = vec![Node::new(&self.track, state.clone(), 0, None)],
+        }
+        self.searched_roles = self.roles;
+        self.low = Scalar::MAX;
+        self.high = Scalar::MIN;
+
+        loop {
+            self.iterate();
+            clock.tick();
+            if clock.is_over() {
+                break;
+            }
+        }
+
+        // The root always has a child after one iteration, the second level may not
+        let first = self.most_visited(0).unwrap_or(0);
+        let first_command = self.nodes[0].options[first].clone();
+        let first_node = self.nodes[0].children[first];
+        let second = self.most_visited(first_node).unwrap_or(0);
+        let second_command = self.nodes[first_node].options[second].clone();
+        self.played = self.nodes[first_node].children.get(second).copied();
+
+        [first_command, second_command]
+    }
+}
+
+// Commands the pod picks from: the racing line, the leading enemy's next
+// checkpoint to block it, or a full turn either way, each with every legal action
+fn pod_options(track: &Track, state: &GameState<4>, index: usize) -> Vec<Command> {
+    let pod = &state.pods[index];
+    let checkpoint = &track.checkpoints[pod.next_checkpoint];
+    let following = if track.is_final_checkpoint(pod.checkpoints_passed) {
+        None
+    } else {
+        Some(track.checkpoints.next(pod.next_checkpoint, 1))
+    };
+    let enemy = &state.pods[2 + leading_pod(track, &state.pods[2..])];
+    let turn = |rotation| move_command(pod, &Move { rotation, action: Action::Thrust(MAX_THRUST) }).target;
+    let targets = [
+        gate_target(&pod.position, &pod.speed, checkpoint, following),
+        track.checkpoints[enemy.next_checkpoint].to_position(),
+        turn(-MAX_ROTATION),
+        turn(MAX_ROTATION),
+    ];
+
+    let actions = legal_actions(pod);
+    let mut options = Vec::with_capacity(targets.len() * actions.len());
+    for target in &targets {
+        for &action in &actions {
+            options.push(Command::new(target.clone(), action));
+        }
+    }
+    options
+}
+
+// Lets every pod rush on up to the horizon, then scores the state. Pod 1
+// rushes as well when the node still waits for its command.
+fn playout(track: &Track, node: &Node, roles: &[Role; 2]) -> Scalar {
+    let mut state = node.state.clone();
+    for turn in node.depth..HORIZON_TURNS {
+        let mut commands = rush_commands(track, &state);
+        if let (Some(first), true) = (&node.first, turn == node.depth) {
+            commands[0] = first.clone();
+        }
+        state = simulate(track, &state, &commands);
+    }
+    evaluate(track, &state, roles)
+}
+
+// Whether the observed pods are where the tree predicted them
+fn same_pods(predicted: &GameState<4>, observed: &GameState<4>) -> bool {
+    predicted.pods.iter().zip(&observed.pods).all(|(predicted, observed)| {
+        predicted.position == observed.position
+            && predicted.speed == observed.speed
+            && predicted.angle == observed.angle
+            && predicted.next_checkpoint == observed.next_checkpoint
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::angle::Degrees;
+    use crate::clock::{FIRST_TURN_LIMIT, TURN_LIMIT};
+    use crate::pod::PodInfo;
+    use crate::vector::Vector;
+
+    #[test]
+    fn carries_the_played_subtree_over() {
+        let checkpoints = vec![Vector::new(12000.0, 2000.0), Vector::new(12000.0, 7000.0), Vector::new(3000.0, 5000.0)];
+        let track = Track::new(3, checkpoints);
+        let pod = |y| PodInfo::new(3000.0, y, 0.0, 0.0, Degrees(0.0), 0);
+        let state = GameState {
+            pods: [pod(4000.0), pod(5000.0), pod(6000.0), pod(7000.0)],
+            turn: 1,
+        };
+        // A few milliseconds of search a turn
+        let search_time = Duration::from_millis(10);
+        let mut clock = TurnClock::with_margins(FIRST_TURN_LIMIT - search_time, TURN_LIMIT - search_time);
+        let mut planner = MonteCarloPlanner::new(track);
+
+        clock.start_turn();
+        planner.plan(&state, &OpponentModel::new(), &clock);
+        let played = planner.played.unwrap();
+        let played_visits = planner.nodes[played].visits;
+        let played_state = planner.nodes[played].state.clone();
+        assert!(played_visits > 0.0);
+
+        clock.start_turn();
+        planner.plan(&played_state, &OpponentModel::new(), &clock);
+
+        // Every iteration of the second turn went through the reused root
+        assert_eq!(planner.nodes[0].visits, played_visits + clock.iterations() as Scalar);
+        assert!(same_pods(&planner.nodes[0].state, &played_state));
+        // Children come after their parent, each reached from one parent only
+        let mut parents = vec![0; planner.nodes.len()];
+        for (index, node) in planner.nodes.iter().enumerate() {
+            for &child in &node.children {
+                assert!(index < child && child < planner.nodes.len());
+                parents[child] += 1;
+                let turns = if node.first.is_some() { 1 } else { 0 };
+                assert_eq!(planner.nodes[child].depth, node.depth + turns);
+            }
+        }
+        assert_eq!(parents[0], 0);
+        assert!(parents[1..].iter().all(|&count| count == 1));
+    }
+}
@@ -0,0 +1,82 @@
+use crate::angle::Degrees;
+use crate::command::{Action, Command};
+use crate::constants::MAX_THRUST;
+use crate::physics::angle_to;
+use crate::pod::PodInfo;
+use crate::simulation::GameState;
+use crate::track::Track;
+use crate::vector::{Scalar, Vector};
+
+// #########################################
+// ##                Moves                ##
+// #########################################
+// What the search planners decide for a pod each turn, turned into the
+// command line the referee expects.
+
+// Distance of the aimed point, far enough for the rotation to be exact
+pub const AIM_DISTANCE: Scalar = 10000.0;
+
+// What the search planners pick from, full thrust first
+pub const ACTIONS: [Action; 4] = [Action::Thrust(MAX_THRUST), Action::Thrust(0), Action::Boost, Action::Shield];
+
+// One turn of one pod: rotation, then what to do
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Move {
+    pub rotation: Degrees,
+    pub action: Action,
+}
+
+// Output line giving the move's rotation and action
+pub fn move_command(pod: &PodInfo, pod_move: &Move) -> Command {
+    let heading = (pod.angle + pod_move.rotation).to_radians();
+    let target = pod.position + Vector::from_angle(heading) * AIM_DISTANCE;
+    Command::new(target.to_position(), pod_move.action)
+}
+
+// Output line of the move of pod `index` of the state. Rotation is free on the
+// first turn, when the input gives no real heading, so moves then turn from
+// the way to the next checkpoint.
+pub fn turn_command(track: &Track, state: &GameState<4>, index: usize, pod_move: &Move) -> Command {
+    let pod = &state.pods[index];
+    if state.turn > 0 {
+        return move_command(pod, pod_move);
+    }
+    let facing = PodInfo {
+        angle: angle_to(&pod.position, &track.checkpoints[pod.next_checkpoint]),
+        ..pod.clone()
+    };
+    move_command(&facing, pod_move)
+}
+
+// Straight at the next checkpoint, full thrust. What the planners expect from
+// pods they don't search for.
+pub fn rush_command(track: &Track, pod: &PodInfo) -> Command {
+    Command::new(
+        track.checkpoints[pod.next_checkpoint].to_position(),
+        Action::Thrust(MAX_THRUST),
+    )
+}
+
+// Every pod rushing, the planners' guess for the turns they don't search
+pub fn rush_commands(track: &Track, state: &GameState<4>) -> [Command; 4] {
+    [
+        rush_command(track, &state.pods[0]),
+        rush_command(track, &state.pods[1]),
+        rush_command(track, &state.pods[2]),
+        rush_command(track, &state.pods[3]),
+    ]
+}
+
+// Actions the pod can take, boost only while it has some left. While the
+// shield cools down thrust is ignored, so steering is all there is to pick;
+// shielding again would only lock the pod for longer.
+pub fn legal_actions(pod: &PodInfo) -> Vec<Action> {
+    if pod.shield_cooldown > 0 {
+        return ACTIONS[..1].to_vec();
+    }
+    ACTIONS
+        .iter()
+        .copied()
+        .filter(|&action| action != Action::Boost || pod.remaining_boosts > 0)
+        .collect()
+}
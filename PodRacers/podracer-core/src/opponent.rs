@@ -0,0 +1,106 @@
+use crate::pod::PodInfo;
+use crate::roles::leading_pod;
+use crate::track::Track;
+use crate::vector::Scalar;
+
+// #########################################
+// ##            OpponentModel            ##
+// #########################################
+// Share of the latest speed in the running average, per turn
+const SPEED_SMOOTHING: Scalar = 0.2;
+// Floor to the speed estimate, so a stopped enemy still gets an arrival time
+const MIN_SPEED_ESTIMATE: Scalar = 100.0;
+
+// Enemy pods followed from turn to turn
+#[derive(Default)]
+pub struct OpponentModel {
+    pub pods: Vec<PodInfo>,
+    average_speeds: Vec<Scalar>,
+}
+impl OpponentModel {
+    pub fn new() -> OpponentModel {
+        OpponentModel::default()
+    }
+
+    // Takes the freshly parsed state of enemy pod `index`
+    pub fn observe(&mut self, index: usize, observed: PodInfo) {
+        let speed = observed.speed.module();
+        if index >= self.pods.len() {
+            self.pods.push(observed);
+            self.average_speeds.push(speed);
+        } else {
+            self.pods[index].update(observed);
+            self.average_speeds[index] += (speed - self.average_speeds[index]) * SPEED_SMOOTHING;
+        }
+    }
+
+    // Enemy pod furthest in the race
+    pub fn leader(&self, track: &Track) -> usize {
+        leading_pod(track, &self.pods)
+    }
+
+    // Estimated turns for enemy pod `index` to reach the checkpoint `k` gates
+    // after the one it heads to, at its average speed along the route
+    pub fn arrival_turns(&self, track: &Track, index: usize, k: usize) -> Scalar {
+        let pod = &self.pods[index];
+        let mut distance = track.checkpoints[pod.next_checkpoint].distance(&pod.position);
+        for leg in 0..k {
+            let from = track.checkpoints.next(pod.next_checkpoint, leg);
+            let to = track.checkpoints.next(pod.next_checkpoint, leg + 1);
+            distance += to.distance(from);
+        }
+        distance / self.average_speeds[index].max(MIN_SPEED_ESTIMATE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::angle::Degrees;
+    use crate::vector::Vector;
+
+    // Legs of 3000, 4000 and 5000
+    fn track() -> Track {
+        Track::new(3, vec![Vector::new(0.0, 0.0), Vector::new(3000.0, 0.0), Vector::new(3000.0, 4000.0)])
+    }
+
+    #[test]
+    fn follows_checkpoints_and_speed() {
+        let mut opponents = OpponentModel::new();
+        opponents.observe(0, PodInfo::new(0.0, 0.0, 400.0, 0.0, Degrees(0.0), 1));
+        assert_eq!(opponents.pods.len(), 1);
+        assert_eq!(opponents.pods[0].checkpoints_passed, 0);
+
+        opponents.observe(0, PodInfo::new(3000.0, 500.0, 0.0, 900.0, Degrees(90.0), 2));
+        assert_eq!(opponents.pods.len(), 1);
+        assert_eq!(opponents.pods[0].checkpoints_passed, 1);
+        assert_eq!(opponents.pods[0].position, Vector::new(3000.0, 500.0));
+        // Average speed moves from 400 a fifth of the way to 900
+        assert_eq!(opponents.arrival_turns(&track(), 0, 0), 3500.0 / 500.0);
+    }
+
+    #[test]
+    fn estimates_arrival_along_the_route() {
+        let track = track();
+        let mut opponents = OpponentModel::new();
+        opponents.observe(0, PodInfo::new(0.0, 0.0, 400.0, 0.0, Degrees(0.0), 1));
+        opponents.observe(1, PodInfo::new(0.0, 0.0, 0.0, 0.0, Degrees(0.0), 1));
+        assert_eq!(opponents.arrival_turns(&track, 0, 0), 7.5);
+        assert_eq!(opponents.arrival_turns(&track, 0, 1), 17.5);
+        // A stopped pod still gets there some day
+        assert_eq!(opponents.arrival_turns(&track, 1, 0), 3000.0 / MIN_SPEED_ESTIMATE);
+    }
+
+    #[test]
+    fn finds_the_pod_furthest_in_the_race() {
+        let track = track();
+        let mut opponents = OpponentModel::new();
+        opponents.observe(0, PodInfo::new(2500.0, 0.0, 0.0, 0.0, Degrees(0.0), 1));
+        opponents.observe(1, PodInfo::new(0.0, 0.0, 0.0, 0.0, Degrees(0.0), 1));
+        assert_eq!(opponents.leader(&track), 0);
+
+        // Pod 1 passes checkpoint 1 and takes the lead
+        opponents.observe(1, PodInfo::new(3000.0, 200.0, 0.0, 0.0, Degrees(90.0), 2));
+        assert_eq!(opponents.leader(&track), 1);
+    }
+}
@@ -0,0 +1,80 @@
+use crate::angle::Degrees;
+use crate::command::Action;
+use crate::constants::{CHECKPOINT_RADIUS, FRICTION, MAX_ROTATION};
+use crate::geometry::ray_circle_intersection;
+use crate::pod::PodInfo;
+use crate::vector::{Scalar, Vector};
+
+// #########################################
+// ##               Physics               ##
+// #########################################
+// Turn rules of the official referee. A turn is: rotate, thrust, move, end_turn.
+
+// Absolute angle, within [0, 360), of the direction from `from` to `to`
+pub fn angle_to(from: &Vector, to: &Vector) -> Degrees {
+    (*to - *from).heading().to_degrees().heading()
+}
+
+// Signed rotation, within [-180, 180], the pod needs to face the target
+pub fn diff_angle(pod: &PodInfo, target: &Vector) -> Degrees {
+    let angle = angle_to(&pod.position, target);
+    let right = if pod.angle <= angle {
+        angle - pod.angle
+    } else {
+        Degrees(360.0) - pod.angle + angle
+    };
+    let left = if pod.angle >= angle {
+        pod.angle - angle
+    } else {
+        pod.angle + Degrees(360.0) - angle
+    };
+
+    if right < left {
+        right
+    } else {
+        -left
+    }
+}
+
+pub fn rotate(pod: &mut PodInfo, target: &Vector) {
+    let rotation = diff_angle(pod, target).clamp(-MAX_ROTATION, MAX_ROTATION);
+    pod.angle = (pod.angle + rotation).heading();
+}
+
+// Turns the pod straight to the target, as allowed on the very first turn
+pub fn face(pod: &mut PodInfo, target: &Vector) {
+    pod.angle = angle_to(&pod.position, target);
+}
+
+// Thrust step of a turn, honouring boosts left and the shield lockout
+pub fn apply_action(pod: &mut PodInfo, action: &Action) {
+    let power = pod.use_action(action);
+    thrust(pod, power);
+}
+
+pub fn thrust(pod: &mut PodInfo, thrust: Scalar) {
+    let radians = pod.angle.to_radians();
+    pod.speed = pod.speed + Vector::new(radians.cos() * thrust, radians.sin() * thrust);
+}
+
+// Moves the pod along its speed for a fraction of the turn
+pub fn advance(pod: &mut PodInfo, time: Scalar) {
+    pod.position = pod.position + pod.speed * time;
+}
+
+// Rounding and friction applied once every pod has moved
+pub fn end_turn(pod: &mut PodInfo) {
+    pod.position = Vector::new(round(pod.position.x), round(pod.position.y));
+    pod.speed = Vector::new((pod.speed.x * FRICTION).trunc(), (pod.speed.y * FRICTION).trunc());
+    pod.angle = Degrees(round(pod.angle.0)).heading();
+}
+
+// Time within [0, limit] at which the pod centre enters the checkpoint, if it does
+pub fn checkpoint_collision_time(pod: &PodInfo, checkpoint: &Vector, limit: Scalar) -> Option<Scalar> {
+    ray_circle_intersection(&pod.position, &pod.speed, checkpoint, CHECKPOINT_RADIUS).filter(|&time| time <= limit)
+}
+
+// Half-up rounding, as Java's Math.round used by the referee
+pub fn round(value: Scalar) -> Scalar {
+    (value + 0.5).floor()
+}
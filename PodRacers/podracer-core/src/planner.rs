@@ -0,0 +1,22 @@
+use crate::clock::TurnClock;
+use crate::command::Command;
+use crate::opponent::OpponentModel;
+use crate::simulation::GameState;
+
+// #########################################
+// ##               Planner               ##
+// #########################################
+// Decision making of the multi-pod bot, so heuristics and searchers can stand
+// in for one another. Pods 0 and 1 of the state are ours, 2 and 3 the enemy
+// ones, with race progress and our own boost and shield bookkeeping kept up to
+// date by the bot.
+//
+// The single-pod bot has no planner: its protocol gives neither the track nor
+// checkpoint indices, so there is no state to search until the track learner
+// has mapped lap 1, and searching against one enemy pod would need every
+// planner written twice. It races on the heuristics' racing line directly.
+pub trait Planner {
+    // Commands of our two pods for this turn, given before the clock runs out.
+    // `opponents` follows the enemy pods of the state since the race started.
+    fn plan(&mut self, state: &GameState<4>, opponents: &OpponentModel, clock: &TurnClock) -> [Command; 2];
+}
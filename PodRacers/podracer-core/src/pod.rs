@@ -0,0 +1,145 @@
+use std::fmt;
+
+use crate::angle::Degrees;
+use crate::command::Action;
+use crate::constants::{BOOSTS_PER_GAME, BOOST_THRUST, MAX_THRUST, SHIELD_COOLDOWN_TURNS, TIMEOUT_TURNS};
+use crate::position::Position;
+use crate::protocol::PodInput;
+use crate::vector::{Scalar, Vector};
+
+// #########################################
+// ##               PodInfo               ##
+// #########################################
+// Full pod state: what the multi-pod protocol gives every turn, plus the race
+// bookkeeping the simulation needs.
+#[derive(Clone, Debug)]
+pub struct PodInfo {
+    pub position: Vector,
+    pub speed: Vector,
+    pub angle: Degrees,
+    pub next_checkpoint: usize,
+    pub remaining_boosts: i32,
+    // SHIELD active this turn, making the pod ten times heavier
+    pub shield: bool,
+    // Turns left without being able to thrust after a SHIELD
+    pub shield_cooldown: i32,
+    pub checkpoints_passed: usize,
+    // Turns left to pass a checkpoint before timing out
+    pub timeout: i32,
+}
+impl PodInfo {
+    pub fn new(x: Scalar, y: Scalar, speed_x: Scalar, speed_y: Scalar, angle: Degrees, next_checkpoint: usize) -> PodInfo {
+        PodInfo {
+            position: Vector::new(x, y),
+            speed: Vector::new(speed_x, speed_y),
+            angle,
+            next_checkpoint,
+            remaining_boosts: BOOSTS_PER_GAME,
+            shield: false,
+            shield_cooldown: 0,
+            checkpoints_passed: 0,
+            timeout: TIMEOUT_TURNS,
+        }
+    }
+    // Takes the observed fields of a freshly parsed pod, keeping our own bookkeeping
+    pub fn update(&mut self, observed: PodInfo) {
+        if observed.next_checkpoint != self.next_checkpoint {
+            self.checkpoints_passed += 1;
+            self.timeout = TIMEOUT_TURNS;
+        } else {
+            self.timeout -= 1;
+        }
+        self.position = observed.position;
+        self.speed = observed.speed;
+        self.angle = observed.angle;
+        self.next_checkpoint = observed.next_checkpoint;
+    }
+    // Spends the boost or shield the action asks for, and returns the thrust it
+    // really gives under the game rules
+    pub fn use_action(&mut self, action: &Action) -> Scalar {
+        let locked = self.shield_cooldown > 0;
+        if locked {
+            self.shield_cooldown -= 1;
+        }
+        self.shield = false;
+
+        match action {
+            Action::Shield => {
+                self.shield = true;
+                self.shield_cooldown = SHIELD_COOLDOWN_TURNS;
+                0.0
+            }
+            _ if locked => 0.0,
+            Action::Thrust(thrust) => (*thrust).min(MAX_THRUST) as Scalar,
+            Action::Boost if self.remaining_boosts > 0 => {
+                self.remaining_boosts -= 1;
+                BOOST_THRUST
+            }
+            Action::Boost => MAX_THRUST as Scalar,
+        }
+    }
+}
+impl From<&PodInput> for PodInfo {
+    fn from(input: &PodInput) -> PodInfo {
+        PodInfo::new(
+            input.position.x as Scalar,
+            input.position.y as Scalar,
+            input.speed.x as Scalar,
+            input.speed.y as Scalar,
+            Degrees(input.angle as Scalar),
+            input.next_checkpoint,
+        )
+    }
+}
+impl fmt::Display for PodInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "position: {}, speed: {}, angle: {}, next_checkpoint: {}, checkpoints_passed: {}, timeout: {}, remaining_boosts: {}, shield_cooldown: {}",
+            self.position,
+            self.speed,
+            self.angle,
+            self.next_checkpoint,
+            self.checkpoints_passed,
+            self.timeout,
+            self.remaining_boosts,
+            self.shield_cooldown
+        )
+    }
+}
+
+// #########################################
+// ##              PodTracker             ##
+// #########################################
+// Pod seen only through its position, as in the single-pod protocol. Speed is
+// derived from the last two positions.
+#[derive(Debug)]
+pub struct PodTracker {
+    pub last_position: Option<Position>,
+    pub position: Option<Position>,
+    pub speed: Vector,
+}
+impl PodTracker {
+    pub fn new() -> PodTracker {
+        PodTracker {
+            last_position: None,
+            position: None,
+            speed: Vector::zero(),
+        }
+    }
+    pub fn new_position(&mut self, x: i32, y: i32) {
+        self.last_position = self.position.take();
+        let position = Position::new(x, y);
+
+        self.speed = match &self.last_position {
+            Some(last_position) => Vector::new((position.x - last_position.x) as Scalar, (position.y - last_position.y) as Scalar),
+            None => Vector::zero(),
+        };
+        self.position = Some(position);
+    }
+}
+impl Default for PodTracker {
+    fn default() -> PodTracker {
+        PodTracker::new()
+    }
+}
@@ -0,0 +1,100 @@
+use crate::command::{Action, Command};
+use crate::constants::{MAX_THRUST, POD_RADIUS};
+use crate::pod::PodInfo;
+use crate::simulation::play_turn;
+use crate::track::Track;
+use crate::vector::Vector;
+
+// #########################################
+// ##             Prediction              ##
+// #########################################
+// Turns of enemy movement predicted for interceptions
+pub const PREDICTION_TURNS: usize = 8;
+
+// Where we can meet an enemy, and how many turns from now
+#[derive(Clone, Debug)]
+pub struct Interception {
+    pub turn: usize,
+    pub point: Vector,
+}
+
+// Positions of the enemy over the next `turns` turns, assuming it heads to its
+// next checkpoint at full thrust. Other pods are left out of the simulation.
+pub fn predict_trajectory(track: &Track, enemy: &PodInfo, turns: usize) -> Vec<Vector> {
+    let mut pods = [enemy.clone()];
+    let mut trajectory = Vec::with_capacity(turns);
+    for _ in 0..turns {
+        let checkpoint = &track.checkpoints[pods[0].next_checkpoint];
+        let command = Command::new(checkpoint.to_position(), Action::Thrust(MAX_THRUST));
+        play_turn(track, &mut pods, &[command], false);
+        trajectory.push(pods[0].position);
+    }
+    trajectory
+}
+
+// Earliest turn at which the hunter, rushing straight to the predicted enemy
+// position of that turn, touches the enemy there
+pub fn interception(track: &Track, hunter: &PodInfo, trajectory: &[Vector]) -> Option<Interception> {
+    trajectory.iter().enumerate().find_map(|(index, point)| {
+        let turn = index + 1;
+        let mut pods = [hunter.clone()];
+        let command = Command::new(point.to_position(), Action::Thrust(MAX_THRUST));
+        for _ in 0..turn {
+            play_turn(track, &mut pods, std::slice::from_ref(&command), false);
+        }
+
+        if pods[0].position.distance(point) <= 2.0 * POD_RADIUS {
+            Some(Interception {
+                turn,
+                point: *point,
+            })
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::angle::Degrees;
+    use crate::vector::Scalar;
+
+    // Checkpoint 0 straight east of the enemy, which starts at rest
+    fn track() -> Track {
+        Track::new(3, vec![Vector::new(15000.0, 5000.0), Vector::new(1000.0, 1000.0)])
+    }
+
+    fn enemy() -> PodInfo {
+        PodInfo::new(0.0, 5000.0, 0.0, 0.0, Degrees(0.0), 0)
+    }
+
+    #[test]
+    fn predicts_a_straight_line_to_the_checkpoint() {
+        let trajectory = predict_trajectory(&track(), &enemy(), PREDICTION_TURNS);
+        let distances: Vec<Scalar> = trajectory.iter().map(|point| point.x).collect();
+        assert_eq!(distances, vec![100.0, 285.0, 542.0, 860.0, 1230.0, 1644.0, 2095.0, 2578.0]);
+        assert!(trajectory.iter().all(|point| point.y == 5000.0));
+    }
+
+    #[test]
+    fn meets_the_enemy_head_on() {
+        let track = track();
+        let trajectory = predict_trajectory(&track, &enemy(), PREDICTION_TURNS);
+        // Facing the enemy from 3000 away, the hunter covers the same distance
+        // it does each turn: 2 * 860 leaves 1280 between them after 4 turns, and
+        // 2 * 1230 leaves 540 after 5, within touching range
+        let hunter = PodInfo::new(3000.0, 5000.0, 0.0, 0.0, Degrees(180.0), 0);
+        let meeting = interception(&track, &hunter, &trajectory).unwrap();
+        assert_eq!(meeting.turn, 5);
+        assert_eq!(meeting.point, Vector::new(1230.0, 5000.0));
+    }
+
+    #[test]
+    fn misses_an_enemy_out_of_reach() {
+        let track = track();
+        let trajectory = predict_trajectory(&track, &enemy(), PREDICTION_TURNS);
+        let hunter = PodInfo::new(6000.0, 5000.0, 0.0, 0.0, Degrees(180.0), 0);
+        assert!(interception(&track, &hunter, &trajectory).is_none());
+    }
+}
@@ -0,0 +1,291 @@
+use std::fmt;
+use std::io::{self, BufRead};
+
+use crate::position::Position;
+
+// #########################################
+// ##           Protocol reader           ##
+// #########################################
+// Game input, read from any buffered source into typed values. Fields may be
+// separated by any whitespace and blank lines are skipped; anything else
+// unexpected is reported with the line it was found on.
+
+// Initialization block of the multi-pod game
+#[derive(Clone, Debug, PartialEq)]
+pub struct RaceInput {
+    pub laps: usize,
+    pub checkpoints: Vec<Position>,
+}
+
+// A "x y vx vy angle nextCheckPointId" line of the multi-pod game
+#[derive(Clone, Debug, PartialEq)]
+pub struct PodInput {
+    pub position: Position,
+    pub speed: Position,
+    pub angle: i32,
+    pub next_checkpoint: usize,
+}
+
+// The two lines of a single-pod turn: our pod and its checkpoint, then the opponent
+#[derive(Clone, Debug, PartialEq)]
+pub struct SinglePodInput {
+    pub position: Position,
+    pub checkpoint: Position,
+    pub checkpoint_distance: i32,
+    pub checkpoint_angle: i32,
+    pub opponent: Position,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct InputError {
+    // Line number, from 1. The end of input counts as the line after the last one.
+    pub line: usize,
+    // Field being read
+    pub field: &'static str,
+    pub problem: String,
+    // The whole line, as received
+    pub text: String,
+}
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, {}: {} (read \"{}\")", self.line, self.field, self.problem, self.text)
+    }
+}
+impl From<InputError> for io::Error {
+    fn from(error: InputError) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, error.to_string())
+    }
+}
+
+pub struct ProtocolReader<R: BufRead> {
+    input: R,
+    // Lines read so far
+    line: usize,
+}
+impl<R: BufRead> ProtocolReader<R> {
+    pub fn new(input: R) -> ProtocolReader<R> {
+        ProtocolReader { input, line: 0 }
+    }
+
+    // Whether the input is over, blank lines aside. That's how a game ends.
+    pub fn at_end(&mut self) -> Result<bool, InputError> {
+        loop {
+            let first = match self.input.fill_buf() {
+                Ok(buffer) => buffer.first().copied(),
+                Err(io_error) => {
+                    return Err(InputError {
+                        line: self.line + 1,
+                        field: "input",
+                        problem: io_error.to_string(),
+                        text: String::new(),
+                    })
+                }
+            };
+            match first {
+                None => return Ok(true),
+                Some(byte) if byte.is_ascii_whitespace() => {
+                    if byte == b'\n' {
+                        self.line += 1;
+                    }
+                    self.input.consume(1);
+                }
+                Some(_) => return Ok(false),
+            }
+        }
+    }
+
+    pub fn read_race(&mut self) -> Result<RaceInput, InputError> {
+        let laps = self.next_line("laps")?.counts(&["laps"])?[0];
+        let line = self.next_line("checkpointCount")?;
+        let checkpoint_count = line.counts(&["checkpointCount"])?[0];
+        // Every lookup on the track wraps around its checkpoints
+        if checkpoint_count < 1 {
+            return Err(line.error("checkpointCount", "a race needs at least one checkpoint".to_string()));
+        }
+        let mut checkpoints = Vec::with_capacity(checkpoint_count);
+        for _ in 0..checkpoint_count {
+            let fields = self.next_line("checkpointX")?.integers(&["checkpointX", "checkpointY"])?;
+            checkpoints.push(Position::new(fields[0], fields[1]));
+        }
+        Ok(RaceInput { laps, checkpoints })
+    }
+
+    // Pod of a race with `checkpoint_count` checkpoints, its next one among them
+    pub fn read_pod(&mut self, checkpoint_count: usize) -> Result<PodInput, InputError> {
+        let line = self.next_line("x")?;
+        let fields = line.integers(&["x", "y", "vx", "vy", "angle", "nextCheckPointId"])?;
+        let next_checkpoint = line.non_negative("nextCheckPointId", fields[5])?;
+        if next_checkpoint >= checkpoint_count {
+            let problem = format!("{} is out of range, the race has {} checkpoints", next_checkpoint, checkpoint_count);
+            return Err(line.error("nextCheckPointId", problem));
+        }
+        Ok(PodInput {
+            position: Position::new(fields[0], fields[1]),
+            speed: Position::new(fields[2], fields[3]),
+            angle: fields[4],
+            next_checkpoint,
+        })
+    }
+
+    pub fn read_single_pod(&mut self) -> Result<SinglePodInput, InputError> {
+        let fields = self.next_line("x")?.integers(&[
+            "x",
+            "y",
+            "nextCheckpointX",
+            "nextCheckpointY",
+            "nextCheckpointDist",
+            "nextCheckpointAngle",
+        ])?;
+        let opponent = self.next_line("opponentX")?.integers(&["opponentX", "opponentY"])?;
+        Ok(SinglePodInput {
+            position: Position::new(fields[0], fields[1]),
+            checkpoint: Position::new(fields[2], fields[3]),
+            checkpoint_distance: fields[4],
+            checkpoint_angle: fields[5],
+            opponent: Position::new(opponent[0], opponent[1]),
+        })
+    }
+
+    // Next line with anything on it, `field` being the first one expected
+    fn next_line(&mut self, field: &'static str) -> Result<Line, InputError> {
+        loop {
+            let mut text = String::new();
+            let read = self.input.read_line(&mut text);
+            self.line += 1;
+            let error = |problem: String| InputError {
+                line: self.line,
+                field,
+                problem,
+                text: String::new(),
+            };
+            match read {
+                Ok(0) => return Err(error("end of input".to_string())),
+                Ok(_) if text.trim().is_empty() => continue,
+                Ok(_) => {
+                    return Ok(Line {
+                        number: self.line,
+                        text: text.trim().to_string(),
+                    })
+                }
+                Err(io_error) => return Err(error(io_error.to_string())),
+            }
+        }
+    }
+}
+
+// +--------------------------+
+// |       Line parsing       |
+// +--------------------------+
+struct Line {
+    number: usize,
+    text: String,
+}
+impl Line {
+    fn error(&self, field: &'static str, problem: String) -> InputError {
+        InputError {
+            line: self.number,
+            field,
+            problem,
+            text: self.text.clone(),
+        }
+    }
+
+    // One integer per field name, nothing more
+    fn integers(&self, fields: &[&'static str]) -> Result<Vec<i32>, InputError> {
+        let mut values = self.text.split_whitespace();
+        let mut integers = Vec::with_capacity(fields.len());
+        for &field in fields {
+            let value = values
+                .next()
+                .ok_or_else(|| self.error(field, "missing".to_string()))?;
+            let integer = value
+                .parse::<i32>()
+                .map_err(|_| self.error(field, format!("\"{}\" is not an integer", value)))?;
+            integers.push(integer);
+        }
+        if let Some(extra) = values.next() {
+            let last = fields[fields.len() - 1];
+            return Err(self.error(last, format!("unexpected \"{}\" after it", extra)));
+        }
+        Ok(integers)
+    }
+
+    fn counts(&self, fields: &[&'static str]) -> Result<Vec<usize>, InputError> {
+        let integers = self.integers(fields)?;
+        fields
+            .iter()
+            .zip(integers)
+            .map(|(field, integer)| self.non_negative(field, integer))
+            .collect()
+    }
+
+    fn non_negative(&self, field: &'static str, integer: i32) -> Result<usize, InputError> {
+        if integer < 0 {
+            return Err(self.error(field, format!("{} is negative", integer)));
+        }
+        Ok(integer as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reader(text: &str) -> ProtocolReader<&[u8]> {
+        ProtocolReader::new(text.as_bytes())
+    }
+
+    #[test]
+    fn reads_fields_separated_by_any_whitespace() {
+        let mut input = reader("  3\n\n2 \n1000\t4500\n\n\n 8000   1500  \n");
+        let race = input.read_race().unwrap();
+        assert_eq!(race.laps, 3);
+        assert_eq!(race.checkpoints, [Position::new(1000, 4500), Position::new(8000, 1500)]);
+        assert!(input.at_end().unwrap());
+    }
+
+    #[test]
+    fn skips_blank_lines_between_turns() {
+        let mut input = reader("\n\n1000 4000 8000 1500 7566 -23\n\n1000 5000\n\n");
+        assert!(!input.at_end().unwrap());
+        let turn = input.read_single_pod().unwrap();
+        assert_eq!(turn.checkpoint_angle, -23);
+        assert_eq!(turn.opponent, Position::new(1000, 5000));
+        assert!(input.at_end().unwrap());
+    }
+
+    #[test]
+    fn reports_missing_and_extra_fields() {
+        let missing = reader("1000 4000 0 0 90\n").read_pod(3).unwrap_err();
+        assert_eq!((missing.line, missing.field), (1, "nextCheckPointId"));
+        assert_eq!(missing.problem, "missing");
+
+        let extra = reader("\n1000 4000 0 0 90 1 7\n").read_pod(3).unwrap_err();
+        assert_eq!((extra.line, extra.field), (2, "nextCheckPointId"));
+        assert_eq!(extra.text, "1000 4000 0 0 90 1 7");
+    }
+
+    #[test]
+    fn rejects_negative_and_empty_counts() {
+        let negative = reader("-3\n").read_race().unwrap_err();
+        assert_eq!((negative.line, negative.field), (1, "laps"));
+
+        let empty = reader("3\n0\n").read_race().unwrap_err();
+        assert_eq!((empty.line, empty.field), (2, "checkpointCount"));
+    }
+
+    #[test]
+    fn rejects_checkpoints_out_of_the_race() {
+        let error = reader("1000 4000 0 0 90 7\n").read_pod(3).unwrap_err();
+        assert_eq!((error.line, error.field), (1, "nextCheckPointId"));
+        assert!(reader("1000 4000 0 0 90 2\n").read_pod(3).is_ok());
+    }
+
+    #[test]
+    fn reports_the_end_of_input() {
+        let error = reader("3\n2\n1000 4500\n").read_race().unwrap_err();
+        assert_eq!((error.line, error.field), (4, "checkpointX"));
+        assert_eq!(error.problem, "end of input");
+        assert!(reader("").at_end().unwrap());
+    }
+}
@@ -0,0 +1,31 @@
+use crate::vector::Scalar;
+
+// #########################################
+// ##                 Rng                 ##
+// #########################################
+// Small seeded xorshift generator, so runs are reproducible without extra crates.
+pub struct Rng {
+    state: u64,
+}
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        // A zero state would stay zero forever
+        Rng {
+            state: seed ^ 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+    // Uniform in [0, 1)
+    pub fn next_float(&mut self) -> Scalar {
+        (self.next_u64() >> 40) as Scalar / (1u64 << 24) as Scalar
+    }
+    // Uniform in [low, high]
+    pub fn range(&mut self, low: i32, high: i32) -> i32 {
+        low + (self.next_u64() % ((high - low) as u64 + 1)) as i32
+    }
+}
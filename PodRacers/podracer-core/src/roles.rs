@@ -0,0 +1,98 @@
+use crate::pod::PodInfo;
+use crate::track::Track;
+use crate::vector::Scalar;
+
+// #########################################
+// ##                Roles                ##
+// #########################################
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Role {
+    // Races for the win
+    Runner,
+    // Gets in the way of the leading enemy
+    Hunter,
+}
+
+// Lead, in distance left to race, the hunter needs before taking over as
+// runner, so roles don't flap between two pods racing side by side
+const SWAP_MARGIN: Scalar = 2000.0;
+
+// Picks the runner and the hunter among our two pods, turn after turn
+pub struct RoleAssigner {
+    runner: usize,
+}
+impl RoleAssigner {
+    pub fn new() -> RoleAssigner {
+        RoleAssigner { runner: 0 }
+    }
+    pub fn assign(&mut self, track: &Track, pods: &[PodInfo]) -> [Role; 2] {
+        let other = 1 - self.runner;
+        if track.remaining_distance(&pods[other]) + SWAP_MARGIN < track.remaining_distance(&pods[self.runner]) {
+            self.runner = other;
+        }
+
+        let mut roles = [Role::Hunter; 2];
+        roles[self.runner] = Role::Runner;
+        roles
+    }
+}
+impl Default for RoleAssigner {
+    fn default() -> RoleAssigner {
+        RoleAssigner::new()
+    }
+}
+
+// Index of the pod furthest in the race
+pub fn leading_pod(track: &Track, pods: &[PodInfo]) -> usize {
+    (0..pods.len())
+        .min_by(|&a, &b| {
+            track
+                .remaining_distance(&pods[a])
+                .total_cmp(&track.remaining_distance(&pods[b]))
+        })
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::angle::Degrees;
+    use crate::vector::Vector;
+
+    fn track() -> Track {
+        Track::new(3, vec![Vector::new(10000.0, 5000.0), Vector::new(2000.0, 5000.0)])
+    }
+
+    // Pods on their way to checkpoint 0, `x` further east being further ahead
+    fn pods(x0: Scalar, x1: Scalar) -> Vec<PodInfo> {
+        vec![
+            PodInfo::new(x0, 5000.0, 0.0, 0.0, Degrees(0.0), 0),
+            PodInfo::new(x1, 5000.0, 0.0, 0.0, Degrees(0.0), 0),
+        ]
+    }
+
+    #[test]
+    fn swaps_roles_past_the_margin_only() {
+        let track = track();
+        let mut roles = RoleAssigner::new();
+        assert_eq!(roles.assign(&track, &pods(5000.0, 5000.0)), [Role::Runner, Role::Hunter]);
+        // The hunter ahead, but by less than SWAP_MARGIN
+        assert_eq!(roles.assign(&track, &pods(5000.0, 6500.0)), [Role::Runner, Role::Hunter]);
+        assert_eq!(roles.assign(&track, &pods(5000.0, 7500.0)), [Role::Hunter, Role::Runner]);
+        // The new runner keeps its role while the other barely passes it
+        assert_eq!(roles.assign(&track, &pods(8500.0, 7500.0)), [Role::Hunter, Role::Runner]);
+        assert_eq!(roles.assign(&track, &pods(9600.0, 7500.0)), [Role::Runner, Role::Hunter]);
+    }
+
+    #[test]
+    fn finds_the_leading_pod() {
+        let track = track();
+        assert_eq!(leading_pod(&track, &pods(5000.0, 6000.0)), 1);
+        assert_eq!(leading_pod(&track, &pods(6000.0, 5000.0)), 0);
+
+        let mut pods = pods(9000.0, 3000.0);
+        pods[1].next_checkpoint = 1;
+        pods[1].checkpoints_passed = 1;
+        assert_eq!(leading_pod(&track, &pods), 1);
+    }
+}
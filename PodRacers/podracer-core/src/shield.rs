@@ -0,0 +1,45 @@
+use crate::collision::{bounce, collision_time};
+use crate::physics::advance;
+use crate::pod::PodInfo;
+use crate::vector::{Scalar, Vector};
+
+// #########################################
+// ##               Shield                ##
+// #########################################
+// Speed towards the checkpoint a hit must cost us before a shield pays for
+// the 3 turns without thrust
+const HURTING_SPEED_LOSS: Scalar = 250.0;
+
+// Whether an enemy is about to hit the pod hard enough to be worth a SHIELD
+pub fn should_shield(pod: &PodInfo, enemies: &[PodInfo], checkpoint: &Vector) -> bool {
+    if pod.shield_cooldown > 0 {
+        return false;
+    }
+    enemies.iter().any(|enemy| speed_loss(pod, enemy, checkpoint) > HURTING_SPEED_LOSS)
+}
+
+// Speed towards the checkpoint lost by an unshielded hit within the next turn,
+// assuming both pods keep their current speed
+fn speed_loss(pod: &PodInfo, enemy: &PodInfo, checkpoint: &Vector) -> Scalar {
+    let time = match collision_time(pod, enemy, 1.0) {
+        Some(time) => time,
+        None => return 0.0,
+    };
+
+    let mut pod = pod.clone();
+    let mut enemy = enemy.clone();
+    pod.shield = false;
+    advance(&mut pod, time);
+    advance(&mut enemy, time);
+
+    let direction = *checkpoint - pod.position;
+    if direction.is_zero() {
+        return 0.0;
+    }
+    let direction = direction.normalize_or_zero();
+    let before = pod.speed.dot_product(&direction);
+    bounce(&mut pod, &mut enemy);
+    let after = pod.speed.dot_product(&direction);
+
+    before - after
+}
@@ -0,0 +1,143 @@
+use crate::collision::{bounce, collision_time};
+use crate::command::Command;
+use crate::constants::TIMEOUT_TURNS;
+use crate::physics;
+use crate::pod::PodInfo;
+use crate::track::Track;
+use crate::vector::{Scalar, Vector};
+
+// #########################################
+// ##             Simulation              ##
+// #########################################
+// Whole-turn resolution, shared by the referee and by any lookahead, so both
+// always agree on where the pods end up.
+
+#[derive(Clone, Debug)]
+pub struct GameState<const N: usize> {
+    pub pods: [PodInfo; N],
+    // Turns already played, rotation is free on turn 0
+    pub turn: usize,
+}
+
+// State after one turn in which pod i follows commands[i]
+pub fn simulate<const N: usize>(track: &Track, state: &GameState<N>, commands: &[Command; N]) -> GameState<N> {
+    let mut next = state.clone();
+    play_turn(track, &mut next.pods, commands, state.turn == 0);
+    next.turn += 1;
+    next
+}
+
+// Plays one turn in place. Returns the first pod, in time order, to complete
+// the race during this turn.
+pub fn play_turn(track: &Track, pods: &mut [PodInfo], commands: &[Command], first_turn: bool) -> Option<usize> {
+    for (pod, command) in pods.iter_mut().zip(commands) {
+        let target = Vector::from(&command.target);
+        if first_turn {
+            physics::face(pod, &target);
+        } else {
+            physics::rotate(pod, &target);
+        }
+        physics::apply_action(pod, &command.action);
+    }
+
+    let finisher = move_pods(track, pods);
+
+    for pod in pods.iter_mut() {
+        physics::end_turn(pod);
+        pod.timeout -= 1;
+    }
+    finisher
+}
+
+// Something happening to the pods part way through a turn
+#[derive(Clone, Copy, Debug)]
+enum Event {
+    Checkpoint(usize),
+    Collision(usize, usize),
+}
+
+// Moves every pod through the turn, handling checkpoints and collisions in time order
+fn move_pods(track: &Track, pods: &mut [PodInfo]) -> Option<usize> {
+    let mut finisher = None;
+    let mut time = 0.0;
+    while time < 1.0 {
+        let remaining = 1.0 - time;
+
+        let mut first_event: Option<(Event, Scalar)> = None;
+        for index in 0..pods.len() {
+            let pod = &pods[index];
+            // A pod done with the race has no checkpoint left to pass
+            let racing = pod.checkpoints_passed < track.race_length();
+            let checkpoint = &track.checkpoints[pod.next_checkpoint];
+            if let Some(event_time) = physics::checkpoint_collision_time(pod, checkpoint, remaining).filter(|_| racing) {
+                if first_event.map_or(true, |(_, first_time)| event_time < first_time) {
+                    first_event = Some((Event::Checkpoint(index), event_time));
+                }
+            }
+
+            for (other, other_pod) in pods.iter().enumerate().skip(index + 1) {
+                if let Some(event_time) = collision_time(pod, other_pod, remaining) {
+                    if first_event.map_or(true, |(_, first_time)| event_time < first_time) {
+                        first_event = Some((Event::Collision(index, other), event_time));
+                    }
+                }
+            }
+        }
+
+        let step = first_event.map_or(remaining, |(_, event_time)| event_time);
+        for pod in pods.iter_mut() {
+            physics::advance(pod, step);
+        }
+        time += step;
+
+        match first_event {
+            Some((Event::Checkpoint(index), _)) => {
+                pass_checkpoint(track, &mut pods[index]);
+                if finisher.is_none() && pods[index].checkpoints_passed == track.race_length() {
+                    finisher = Some(index);
+                }
+            }
+            Some((Event::Collision(first, second), _)) => {
+                let (left, right) = pods.split_at_mut(second);
+                bounce(&mut left[first], &mut right[0]);
+            }
+            None => break,
+        }
+    }
+    finisher
+}
+
+fn pass_checkpoint(track: &Track, pod: &mut PodInfo) {
+    pod.checkpoints_passed += 1;
+    pod.next_checkpoint = track.checkpoints.next_index(pod.next_checkpoint, 1);
+    pod.timeout = TIMEOUT_TURNS;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::angle::Degrees;
+    use crate::command::Action;
+    use crate::position::Position;
+
+    #[test]
+    fn plays_a_turn_by_the_official_rules() {
+        let track = Track::new(3, vec![Vector::new(8000.0, 8000.0), Vector::new(3000.0, 8000.0)]);
+        let state = GameState {
+            pods: [PodInfo::new(1000.0, 1000.0, -100.0, 50.0, Degrees(0.0), 0)],
+            turn: 1,
+        };
+        let commands = [Command::new(Position::new(1000, 5000), Action::Thrust(100))];
+        let next = simulate(&track, &state, &commands);
+
+        // Turns 18 of the 90 degrees to the target, thrusts 100 along that to a
+        // speed of (-4.89, 80.90), moves to (995.11, 1080.90) and rounds it,
+        // then keeps 0.85 of the speed, truncated towards zero
+        let pod = &next.pods[0];
+        assert_eq!(pod.angle, Degrees(18.0));
+        assert_eq!(pod.position, Vector::new(995.0, 1081.0));
+        assert_eq!(pod.speed, Vector::new(-4.0, 68.0));
+        assert_eq!((pod.next_checkpoint, pod.timeout), (0, TIMEOUT_TURNS - 1));
+        assert_eq!(next.turn, 2);
+    }
+}
@@ -0,0 +1,76 @@
+use crate::angle::Degrees;
+use crate::constants::MAX_ROTATION;
+use crate::moves::AIM_DISTANCE;
+use crate::physics::angle_to;
+use crate::vector::{Scalar, Vector};
+
+// #########################################
+// ##               Steering              ##
+// #########################################
+// A pod turns at most MAX_ROTATION a turn towards the point it is given, then
+// thrusts along its new heading. Steering picks the best heading within reach
+// and the point to output for it.
+
+// Turns of drift the aimed direction is corrected by, so the pod's course and
+// not only its nose comes round to the target
+const DRIFT_COMPENSATION: Scalar = 2.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Steering {
+    // Heading the pod has once rotated, within [0, 360)
+    pub heading: Degrees,
+    // Point to output to get that heading
+    pub target: Vector,
+}
+
+// Signed rotation, within (-180, 180], to face `point`
+pub fn rotation_to(position: &Vector, heading: Degrees, point: &Vector) -> Degrees {
+    (angle_to(position, point) - heading).normalized()
+}
+
+// Turns of rotation until the pod faces `point`, this one included. 0 when it already does.
+pub fn turns_to_face(position: &Vector, heading: Degrees, point: &Vector) -> usize {
+    (rotation_to(position, heading, point).abs() / MAX_ROTATION).ceil() as usize
+}
+
+// Rotation this turn towards `target`, cancelling the sideways drift of `speed`
+pub fn steer(position: &Vector, heading: Degrees, speed: &Vector, target: &Vector) -> Steering {
+    let desired = *target - *speed * DRIFT_COMPENSATION;
+    let rotation = if desired == *position {
+        Degrees(0.0)
+    } else {
+        rotation_to(position, heading, &desired).clamp(-MAX_ROTATION, MAX_ROTATION)
+    };
+    let heading = (heading + rotation).heading();
+    Steering {
+        heading,
+        target: *position + Vector::from_angle(heading.to_radians()) * AIM_DISTANCE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_turns_to_face() {
+        let position = Vector::zero();
+        let ahead = Vector::new(1000.0, 0.0);
+        let behind = Vector::new(-1000.0, 0.0);
+        assert_eq!(turns_to_face(&position, Degrees(0.0), &ahead), 0);
+        assert_eq!(turns_to_face(&position, Degrees(350.0), &ahead), 1);
+        assert_eq!(turns_to_face(&position, Degrees(0.0), &behind), 10);
+        assert_eq!(rotation_to(&position, Degrees(270.0), &ahead), Degrees(90.0));
+    }
+
+    #[test]
+    fn turns_no_further_than_allowed() {
+        let position = Vector::new(5000.0, 5000.0);
+        let steering = steer(&position, Degrees(0.0), &Vector::zero(), &Vector::new(5000.0, 9000.0));
+        assert_eq!(steering.heading, Degrees(18.0));
+
+        let close = steer(&position, Degrees(0.0), &Vector::zero(), &Vector::new(9000.0, 5500.0));
+        assert!((close.heading.0 - 7.125).abs() < 0.01);
+        assert!(rotation_to(&position, close.heading, &Vector::new(9000.0, 5500.0)).abs() < Degrees(0.01));
+    }
+}
@@ -0,0 +1,67 @@
+
+use crate::angle::Radians;
+use crate::constants::CHECKPOINT_RADIUS;
+use crate::geometry::line_intersection;
+use crate::position::Position;
+use crate::vector::{Scalar, Vector, PI};
+
+// #########################################
+// ##               Target                ##
+// #########################################
+// Turns of drift into a checkpoint that make it safe to already turn to the next one
+const DRIFT_TURNS: Scalar = 3.0;
+
+// Point to aim at, planning for the gate after the checkpoint when there is one:
+// once the speed is about to carry the pod into the checkpoint, it already
+// turns towards the following gate.
+pub fn gate_target(position: &Vector, speed: &Vector, checkpoint: &Vector, following: Option<&Vector>) -> Position {
+    match following {
+        Some(following) if drifts_into(position, speed, checkpoint) => following.to_position(),
+        _ => drift_target(position, speed, checkpoint),
+    }
+}
+
+// Whether the current speed alone takes the pod through the checkpoint within DRIFT_TURNS
+fn drifts_into(position: &Vector, speed: &Vector, checkpoint: &Vector) -> bool {
+    if speed.is_zero() {
+        return false;
+    }
+    let to_checkpoint = *checkpoint - *position;
+    let direction = speed.normalize_or_zero();
+    let along = to_checkpoint.dot_product(&direction);
+    let across = direction.determinant(&to_checkpoint).abs();
+
+    along > 0.0 && along < speed.module() * DRIFT_TURNS && across < CHECKPOINT_RADIUS
+}
+
+// Point to aim at so the current speed drifts the pod into the checkpoint.
+pub fn drift_target(position: &Vector, speed: &Vector, checkpoint: &Vector) -> Position {
+    if speed.is_zero() {
+        return checkpoint.to_position();
+    }
+
+    let vector_pod_ch = *checkpoint - *position;
+    let vector_perpendicular = vector_pod_ch.rotate(Radians(PI / 2.0));
+    let angle = vector_pod_ch.get_angle(speed);
+
+    if angle.abs() >= Radians(PI / 2.0) {
+        return checkpoint.to_position();
+    }
+
+    // Intersect:
+    // - (A) Perpendicular line passing by the checkpoint
+    // - (V) The velocity line passing by the pod
+    let vector_intersect = match line_intersection(checkpoint, &vector_perpendicular, position, speed) {
+        Some(intersection) => intersection,
+        None => return checkpoint.to_position(),
+    };
+    // Aiming as far on the other side of the checkpoint corrects the drift
+    let vector_ch_target = *checkpoint - vector_intersect;
+
+    // Allow only a max of checkpoint radius deviation from checkpoint
+    if vector_ch_target.module() > CHECKPOINT_RADIUS {
+        (*checkpoint + vector_ch_target.normalize_or_zero() * CHECKPOINT_RADIUS).to_position()
+    } else {
+        (*checkpoint + vector_ch_target).to_position()
+    }
+}
@@ -0,0 +1,98 @@
+use crate::command::{Action, Command};
+use crate::constants::{FRICTION, MAX_THRUST, TIMEOUT_TURNS};
+use crate::pod::PodInfo;
+use crate::simulation::play_turn;
+use crate::steering::steer;
+use crate::target::gate_target;
+use crate::track::Track;
+use crate::vector::{Scalar, Vector};
+
+// #########################################
+// ##               Throttle              ##
+// #########################################
+// Thrust is picked by rolling the physics out a few turns for every value from
+// 0 to MAX_THRUST: the pod approaches the checkpoint with the candidate, then
+// races on at full thrust to the one after. The value through both soonest
+// wins, so a pod only slows down as much as the coming gate needs.
+
+// Turns simulated for each candidate. Beyond a few, the distance estimate
+// below does better than the crude racing of the rollout.
+const ROLLOUT_TURNS: usize = 3;
+// Distance covered a turn at full thrust, once thrust and friction balance out
+const CRUISE_SPEED: Scalar = MAX_THRUST as Scalar / (1.0 - FRICTION);
+
+// Thrust minimizing the predicted turns to pass `checkpoint` then `following`,
+// when the pod aims at `target` this turn. Without a following checkpoint,
+// only reaching `checkpoint` counts.
+pub fn best_thrust(pod: &PodInfo, target: &Vector, checkpoint: &Vector, following: Option<&Vector>, first_turn: bool) -> u8 {
+    let track = Track::new(1, std::iter::once(*checkpoint).chain(following.copied()).collect());
+
+    // Ties go to the strongest thrust
+    let mut best = (MAX_THRUST, Scalar::INFINITY);
+    for thrust in (0..=MAX_THRUST).rev() {
+        let turns = predicted_turns(&track, pod, target, thrust, first_turn);
+        if turns < best.1 {
+            best = (thrust, turns);
+        }
+    }
+    best.0
+}
+
+// Turns until the pod is through the track's checkpoints, estimated from the
+// distance left when the rollout ends before
+fn predicted_turns(track: &Track, pod: &PodInfo, target: &Vector, thrust: u8, first_turn: bool) -> Scalar {
+    let mut pod = PodInfo {
+        next_checkpoint: 0,
+        checkpoints_passed: 0,
+        timeout: TIMEOUT_TURNS,
+        ..pod.clone()
+    };
+    let mut command = Command::new(target.to_position(), Action::Thrust(thrust));
+
+    for turn in 0..ROLLOUT_TURNS {
+        if play_turn(track, std::slice::from_mut(&mut pod), std::slice::from_ref(&command), first_turn && turn == 0).is_some() {
+            return (turn + 1) as Scalar;
+        }
+        // Racing on the way the bots do, for the gate then the next one
+        let next = pod.next_checkpoint;
+        let following = (next + 1 < track.checkpoints.len()).then(|| &track.checkpoints[next + 1]);
+        let target = gate_target(&pod.position, &pod.speed, &track.checkpoints[next], following);
+        let steering = steer(&pod.position, pod.angle, &pod.speed, &Vector::from(&target));
+        // The candidate holds until the checkpoint: waiting a turn is never a plan
+        let thrust = if pod.checkpoints_passed == 0 { thrust } else { MAX_THRUST };
+        command = Command::new(steering.target.to_position(), Action::Thrust(thrust));
+    }
+    ROLLOUT_TURNS as Scalar + turns_left(track, &pod)
+}
+
+// Turns to race the distance left at full thrust. Short of cruising speed
+// towards the next checkpoint, the pod loses the difference a turn, shrinking
+// with friction as it speeds up.
+fn turns_left(track: &Track, pod: &PodInfo) -> Scalar {
+    let direction = (track.checkpoints[pod.next_checkpoint] - pod.position).normalize_or_zero();
+    let lag = CRUISE_SPEED * FRICTION - pod.speed.dot_product(&direction);
+    (track.remaining_distance(pod) + lag / (1.0 - FRICTION)) / CRUISE_SPEED
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::angle::Degrees;
+
+    #[test]
+    fn rushes_a_checkpoint_far_ahead() {
+        let pod = PodInfo::new(1000.0, 5000.0, 0.0, 0.0, Degrees(0.0), 0);
+        let checkpoint = Vector::new(9000.0, 5000.0);
+        let following = Vector::new(1000.0, 5500.0);
+        assert_eq!(best_thrust(&pod, &checkpoint, &checkpoint, Some(&following), false), MAX_THRUST);
+    }
+
+    #[test]
+    fn slows_down_before_a_hairpin() {
+        // Fast, a turn away from a checkpoint whose following one is straight back
+        let pod = PodInfo::new(7000.0, 5000.0, 600.0, 0.0, Degrees(0.0), 0);
+        let checkpoint = Vector::new(8000.0, 5000.0);
+        let following = Vector::new(2000.0, 5000.0);
+        assert!(best_thrust(&pod, &checkpoint, &checkpoint, Some(&following), false) < MAX_THRUST);
+    }
+}
@@ -0,0 +1,99 @@
+use crate::checkpoint_ring::CheckpointRing;
+use crate::pod::PodInfo;
+use crate::vector::{Scalar, Vector};
+
+// #########################################
+// ##                Track                ##
+// #########################################
+#[derive(Clone, Debug)]
+pub struct Track {
+    pub laps: usize,
+    pub checkpoints: CheckpointRing,
+}
+impl Track {
+    pub fn new(laps: usize, checkpoints: Vec<Vector>) -> Track {
+        Track {
+            laps,
+            checkpoints: CheckpointRing::new(checkpoints),
+        }
+    }
+    // Checkpoints to pass to finish, the start one included at the very end
+    pub fn race_length(&self) -> usize {
+        self.laps * self.checkpoints.len()
+    }
+
+    // +--------------------------+
+    // |       Race progress      |
+    // +--------------------------+
+    // Lap being run, starting at 1
+    pub fn lap(&self, checkpoints_passed: usize) -> usize {
+        (checkpoints_passed / self.checkpoints.len() + 1).min(self.laps)
+    }
+    pub fn checkpoints_left(&self, checkpoints_passed: usize) -> usize {
+        self.race_length().saturating_sub(checkpoints_passed)
+    }
+    // Whether the next checkpoint is the finish line of the last lap
+    pub fn is_final_checkpoint(&self, checkpoints_passed: usize) -> bool {
+        self.checkpoints_left(checkpoints_passed) == 1
+    }
+    // Distance left to race, going through the centre of every checkpoint left
+    pub fn remaining_distance(&self, pod: &PodInfo) -> Scalar {
+        let left = self.checkpoints_left(pod.checkpoints_passed);
+        if left == 0 {
+            return 0.0;
+        }
+
+        let mut distance = self.checkpoints[pod.next_checkpoint].distance(&pod.position);
+        for leg in 0..(left - 1) {
+            let from = self.checkpoints.next(pod.next_checkpoint, leg);
+            let to = self.checkpoints.next(pod.next_checkpoint, leg + 1);
+            distance += to.distance(from);
+        }
+        distance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::angle::Degrees;
+
+    // Two laps of a 3-4-5 triangle, legs of 3000, 4000 and 5000
+    fn track() -> Track {
+        Track::new(2, vec![Vector::new(0.0, 0.0), Vector::new(3000.0, 0.0), Vector::new(3000.0, 4000.0)])
+    }
+
+    fn pod(x: Scalar, y: Scalar, next_checkpoint: usize, checkpoints_passed: usize) -> PodInfo {
+        let mut pod = PodInfo::new(x, y, 0.0, 0.0, Degrees(0.0), next_checkpoint);
+        pod.checkpoints_passed = checkpoints_passed;
+        pod
+    }
+
+    #[test]
+    fn counts_laps_and_checkpoints_left() {
+        let track = track();
+        assert_eq!(track.race_length(), 6);
+        assert_eq!((track.lap(0), track.lap(2), track.lap(3), track.lap(6)), (1, 1, 2, 2));
+        assert_eq!((track.checkpoints_left(0), track.checkpoints_left(5), track.checkpoints_left(7)), (6, 1, 0));
+    }
+
+    #[test]
+    fn finds_the_final_gate_of_the_last_lap() {
+        let track = track();
+        // Checkpoint 0 ends lap 1 without being the finish line
+        assert!(!track.is_final_checkpoint(2));
+        assert!(!track.is_final_checkpoint(4));
+        assert!(track.is_final_checkpoint(5));
+        assert!(!track.is_final_checkpoint(6));
+    }
+
+    #[test]
+    fn measures_the_distance_left() {
+        let track = track();
+        assert_eq!(track.remaining_distance(&pod(0.0, 0.0, 1, 0)), 24000.0);
+        assert_eq!(track.remaining_distance(&pod(3000.0, 1000.0, 2, 4)), 3000.0 + 5000.0);
+        // Final gate, then finished
+        assert_eq!(track.remaining_distance(&pod(3000.0, 4000.0, 0, 5)), 5000.0);
+        assert_eq!(track.remaining_distance(&pod(0.0, 0.0, 1, 6)), 0.0);
+    }
+}
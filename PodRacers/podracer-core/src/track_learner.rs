@@ -0,0 +1,239 @@
+use crate::checkpoint_ring::CheckpointRing;
+use crate::track::Track;
+use crate::vector::{Scalar, Vector};
+
+// #########################################
+// ##            Track learner            ##
+// #########################################
+// The single-pod protocol only ever gives the checkpoint to reach, so the track
+// gets mapped while racing lap 1: it is complete once checkpoint 0, where the
+// pods started, is asked for. Official layouts are recognized earlier, from the
+// start line and the first checkpoint, so their route is known from turn 1.
+
+// Layouts of the official CodinGame referee, from checkpoint 0. A race may
+// start from any of their checkpoints, each one moved a little.
+const OFFICIAL_LAYOUTS: [&[(Scalar, Scalar)]; 12] = [
+    &[(12460.0, 1350.0), (10540.0, 5980.0), (3580.0, 5180.0), (13580.0, 7600.0)],
+    &[(4560.0, 2180.0), (7350.0, 4940.0), (3320.0, 7230.0), (14580.0, 7700.0), (10560.0, 5060.0), (13100.0, 2320.0)],
+    &[(5010.0, 5260.0), (11480.0, 6080.0), (9100.0, 1840.0)],
+    &[(14660.0, 1410.0), (3450.0, 7220.0), (9420.0, 7240.0), (5970.0, 4240.0)],
+    &[(3640.0, 4420.0), (8000.0, 7900.0), (13300.0, 5540.0), (9560.0, 1400.0)],
+    &[(4100.0, 7420.0), (13500.0, 2340.0), (12940.0, 7220.0), (5640.0, 2580.0)],
+    &[(14520.0, 7780.0), (6320.0, 4290.0), (7800.0, 860.0), (7660.0, 5970.0), (3140.0, 7540.0), (9520.0, 4380.0)],
+    &[(10040.0, 5970.0), (13920.0, 1940.0), (8020.0, 3260.0), (2670.0, 7020.0)],
+    &[(7500.0, 6940.0), (6000.0, 5360.0), (11300.0, 2820.0)],
+    &[(4060.0, 4660.0), (13040.0, 1900.0), (6560.0, 7840.0), (7480.0, 1360.0), (12700.0, 7100.0)],
+    &[(3020.0, 5190.0), (6280.0, 7760.0), (14100.0, 7760.0), (13880.0, 1220.0), (10240.0, 4920.0), (6100.0, 2200.0)],
+    &[(10323.0, 3366.0), (11203.0, 5425.0), (7259.0, 6656.0), (5425.0, 2838.0)],
+];
+// Farthest a checkpoint may be from its layout position and still match it.
+// Checkpoints of a layout are thousands of units apart, so matches are unique.
+const LAYOUT_TOLERANCE: Scalar = 400.0;
+
+#[derive(Default)]
+pub struct TrackLearner {
+    // Checkpoints in the order they were asked for: 1, 2, ... and 0 last
+    seen: CheckpointRing,
+    // Index in `seen` of the checkpoint to reach
+    current: usize,
+    // Whether lap 1 is over, `seen` then holding the whole track
+    wrapped: bool,
+    // Checkpoint 0, as seen on turn 1, until the mapping starts over
+    start: Option<Vector>,
+    // Recognized official layout, from checkpoint 0, until a checkpoint disagrees
+    layout: Option<Vec<Vector>>,
+    // Whole track from checkpoint 0, once known either way
+    route: Option<CheckpointRing>,
+}
+impl TrackLearner {
+    pub fn new() -> TrackLearner {
+        TrackLearner::default()
+    }
+
+    // Takes the checkpoint to reach this turn. On turn 1 the pods are lined up
+    // on checkpoint 0, which is then halfway between ours and the opponent.
+    pub fn observe(&mut self, checkpoint: &Vector, position: &Vector, opponent: &Vector) {
+        if self.seen.is_empty() {
+            let start = position.lerp(opponent, 0.5);
+            self.layout = recognize(&start, checkpoint);
+            self.start = Some(start);
+        }
+
+        match self.seen.position(checkpoint) {
+            Some(index) => {
+                // Asked for the first checkpoint again: lap 1 is over
+                if index == 0 && self.current != 0 {
+                    self.wrapped = true;
+                }
+                self.current = index;
+            }
+            None => {
+                self.seen.push(*checkpoint);
+                self.current = self.seen.len() - 1;
+                // A lap has no new checkpoint after the first one comes back
+                if self.wrapped {
+                    self.forget();
+                } else if self.current > 0 && self.start.is_some_and(|start| start.distance(checkpoint) <= LAYOUT_TOLERANCE) {
+                    // Asked for the start line: lap 1 ends there
+                    self.wrapped = true;
+                }
+            }
+        }
+
+        // The layout must agree with every checkpoint met so far
+        if let Some(layout) = &self.layout {
+            let expected = &layout[(self.current + 1) % layout.len()];
+            if self.current + 1 > layout.len() || expected.distance(checkpoint) > LAYOUT_TOLERANCE {
+                self.layout = None;
+            }
+        }
+        self.route = self.learnt_route();
+    }
+
+    // Whether the whole track is known, mapped or recognized
+    pub fn is_complete(&self) -> bool {
+        self.route.is_some()
+    }
+
+    // Share of the track's checkpoints actually seen, 0 while its length is unknown
+    pub fn confidence(&self) -> Scalar {
+        match &self.route {
+            Some(route) => self.seen.len() as Scalar / route.len() as Scalar,
+            None => 0.0,
+        }
+    }
+
+    // Checkpoint to reach after the current one, once the track is known
+    pub fn following(&self) -> Option<&Vector> {
+        let route = self.route.as_ref()?;
+        Some(route.next(self.current + 1, 1))
+    }
+
+    pub fn track(&self, laps: usize) -> Option<Track> {
+        let route = self.route.as_ref()?;
+        Some(Track::new(laps, route.iter().cloned().collect()))
+    }
+
+    // Starts mapping over, from the current checkpoint, when the track
+    // turns out different from what was learnt
+    fn forget(&mut self) {
+        let current = self.seen[self.current];
+        self.seen = CheckpointRing::new(vec![current]);
+        self.current = 0;
+        self.wrapped = false;
+        self.start = None;
+        self.layout = None;
+    }
+
+    // Checkpoints from 0: the mapped ones after a lap, else the recognized
+    // layout, corrected by the checkpoints seen
+    fn learnt_route(&self) -> Option<CheckpointRing> {
+        if self.wrapped {
+            let count = self.seen.len();
+            let route = (0..count).map(|index| *self.seen.next(index, count - 1)).collect();
+            return Some(CheckpointRing::new(route));
+        }
+
+        let layout = self.layout.as_ref()?;
+        let route = (0..layout.len())
+            .map(|index| {
+                // Checkpoint `index` is the one seen in position `index - 1`
+                let seen = (index + layout.len() - 1) % layout.len();
+                if seen < self.seen.len() {
+                    self.seen[seen]
+                } else {
+                    layout[index]
+                }
+            })
+            .collect();
+        Some(CheckpointRing::new(route))
+    }
+}
+
+// Official layout starting at `start` with `first` as checkpoint 1, from
+// checkpoint 0. None unless exactly one layout fits.
+fn recognize(start: &Vector, first: &Vector) -> Option<Vec<Vector>> {
+    let near = |(x, y): (Scalar, Scalar), point: &Vector| Vector::new(x, y).distance(point) <= LAYOUT_TOLERANCE;
+
+    let mut matches = Vec::new();
+    for layout in &OFFICIAL_LAYOUTS {
+        for offset in 0..layout.len() {
+            let zero = layout[offset];
+            let one = layout[(offset + 1) % layout.len()];
+            if near(zero, start) && near(one, first) {
+                let rotated = (0..layout.len())
+                    .map(|index| {
+                        let (x, y) = layout[(offset + index) % layout.len()];
+                        Vector::new(x, y)
+                    })
+                    .collect::<Vec<_>>();
+                matches.push(rotated);
+            }
+        }
+    }
+
+    if matches.len() == 1 {
+        matches.pop()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Feeds the learner the checkpoints to reach, a turn each, pods lined up on `start`
+    fn observe_all(learner: &mut TrackLearner, start: &Vector, checkpoints: &[Vector]) {
+        let side = Vector::new(0.0, 500.0);
+        for checkpoint in checkpoints {
+            learner.observe(checkpoint, &(*start + side), &(*start - side));
+        }
+    }
+
+    #[test]
+    fn maps_an_unknown_track_over_lap_one() {
+        let track = [Vector::new(1000.0, 1000.0), Vector::new(8000.0, 2000.0), Vector::new(5000.0, 7000.0)];
+        let mut learner = TrackLearner::new();
+
+        observe_all(&mut learner, &track[0], &[track[1], track[1], track[2]]);
+        assert!(!learner.is_complete());
+        assert_eq!(learner.following(), None);
+
+        // Back to the start line, the next gate is known for the end of lap 1
+        observe_all(&mut learner, &track[0], &[track[0]]);
+        assert!(learner.is_complete());
+        assert_eq!(learner.confidence(), 1.0);
+        assert_eq!(learner.following(), Some(&track[1]));
+        let learnt = learner.track(3).unwrap();
+        assert_eq!(learnt.checkpoints.iter().cloned().collect::<Vec<_>>(), track);
+
+        observe_all(&mut learner, &track[0], &[track[1]]);
+        assert_eq!(learner.following(), Some(&track[2]));
+        assert_eq!(learner.track(3).unwrap().checkpoints.len(), 3);
+    }
+
+    #[test]
+    fn recognizes_an_official_layout_from_turn_one() {
+        // Third layout, started from its checkpoint 1, checkpoints moved a bit
+        let start = Vector::new(11490.0, 6070.0);
+        let first = Vector::new(9110.0, 1830.0);
+        let mut learner = TrackLearner::new();
+
+        observe_all(&mut learner, &start, &[Vector::new(9110.0, 1830.0)]);
+        assert!(learner.is_complete());
+        assert_eq!(learner.confidence(), 1.0 / 3.0);
+        assert_eq!(learner.following(), Some(&Vector::new(5010.0, 5260.0)));
+        assert_eq!(learner.track(3).unwrap().checkpoints[1], first);
+    }
+
+    #[test]
+    fn drops_a_layout_the_track_disagrees_with() {
+        let start = Vector::new(11490.0, 6070.0);
+        let mut learner = TrackLearner::new();
+
+        observe_all(&mut learner, &start, &[Vector::new(9110.0, 1830.0), Vector::new(2000.0, 2000.0)]);
+        assert!(!learner.is_complete());
+        assert_eq!(learner.confidence(), 0.0);
+    }
+}
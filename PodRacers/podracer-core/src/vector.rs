@@ -0,0 +1,195 @@
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::angle::Radians;
+use crate::position::Position;
+
+// Float type of every coordinate, speed and angle. The official referee
+// computes in f64; the "f64" feature matches it, f32 searches faster.
+#[cfg(not(feature = "f64"))]
+pub type Scalar = f32;
+#[cfg(not(feature = "f64"))]
+pub use std::f32::consts::PI;
+#[cfg(feature = "f64")]
+pub type Scalar = f64;
+#[cfg(feature = "f64")]
+pub use std::f64::consts::PI;
+
+// #########################################
+// ##               Vector                ##
+// #########################################
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Vector {
+    pub x: Scalar,
+    pub y: Scalar,
+}
+impl Vector {
+    pub fn new(x: Scalar, y: Scalar) -> Vector {
+        Vector { x, y }
+    }
+    pub fn zero() -> Vector {
+        Vector { x: 0.0, y: 0.0 }
+    }
+    // Unit vector pointing at the angle from the x axis
+    pub fn from_angle(angle: Radians) -> Vector {
+        Vector {
+            x: angle.cos(),
+            y: angle.sin(),
+        }
+    }
+    pub fn is_zero(&self) -> bool {
+        (self.x == 0.0) && (self.y == 0.0)
+    }
+    pub fn dot_product(&self, other: &Vector) -> Scalar {
+        (self.x * other.x) + (self.y * other.y)
+    }
+    pub fn determinant(&self, other: &Vector) -> Scalar {
+        (self.x * other.y) - (self.y * other.x)
+    }
+    pub fn length_sq(&self) -> Scalar {
+        self.dot_product(self)
+    }
+    pub fn module(&self) -> Scalar {
+        self.length_sq().sqrt()
+    }
+    pub fn distance(&self, other: &Vector) -> Scalar {
+        (*self - *other).module()
+    }
+    // Same direction, length 1. The zero vector has no direction and stays zero.
+    pub fn normalize_or_zero(&self) -> Vector {
+        let module = self.module();
+        if module == 0.0 {
+            Vector::zero()
+        } else {
+            Vector::new(self.x / module, self.y / module)
+        }
+    }
+    // Point at `t` of the way to the other one, past it for t > 1
+    pub fn lerp(&self, other: &Vector, t: Scalar) -> Vector {
+        *self + (*other - *self) * t
+    }
+    pub fn rotate(&self, angle: Radians) -> Vector {
+        let cos = angle.cos();
+        let sin = angle.sin();
+        Vector {
+            x: (self.x * cos) - (self.y * sin),
+            y: (self.x * sin) + (self.y * cos),
+        }
+    }
+    // Signed angle from this vector to the other, within (-PI, PI]
+    pub fn get_angle(&self, other: &Vector) -> Radians {
+        Radians(self.determinant(other).atan2(self.dot_product(other)))
+    }
+    // Direction of the vector from the x axis, within (-PI, PI]
+    pub fn heading(&self) -> Radians {
+        Radians(self.y.atan2(self.x))
+    }
+    pub fn to_position(&self) -> Position {
+        Position::new(self.x as i32, self.y as i32)
+    }
+}
+impl Add for Vector {
+    type Output = Vector;
+    fn add(self, other: Vector) -> Vector {
+        Vector {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+impl Sub for Vector {
+    type Output = Vector;
+    fn sub(self, other: Vector) -> Vector {
+        Vector {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
+impl Mul<Scalar> for Vector {
+    type Output = Vector;
+    fn mul(self, factor: Scalar) -> Vector {
+        Vector {
+            x: self.x * factor,
+            y: self.y * factor,
+        }
+    }
+}
+impl Neg for Vector {
+    type Output = Vector;
+    fn neg(self) -> Vector {
+        Vector { x: -self.x, y: -self.y }
+    }
+}
+impl From<&Position> for Vector {
+    fn from(position: &Position) -> Vector {
+        Vector {
+            x: position.x as Scalar,
+            y: position.y as Scalar,
+        }
+    }
+}
+impl fmt::Display for Vector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({} x {})", self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(vector: Vector, expected: Vector) {
+        assert!(vector.distance(&expected) < 1e-5, "{} instead of {}", vector, expected);
+    }
+
+    #[test]
+    fn normalizes_without_nan() {
+        assert_eq!(Vector::zero().normalize_or_zero(), Vector::zero());
+        assert_eq!(Vector::new(0.0, -5.0).normalize_or_zero(), Vector::new(0.0, -1.0));
+        assert_near(Vector::new(3.0, 4.0).normalize_or_zero(), Vector::new(0.6, 0.8));
+    }
+
+    #[test]
+    fn points_along_angles() {
+        assert_near(Vector::from_angle(Radians(0.0)), Vector::new(1.0, 0.0));
+        assert_near(Vector::from_angle(Radians(PI / 2.0)), Vector::new(0.0, 1.0));
+        assert_near(Vector::from_angle(Radians(-PI)), Vector::new(-1.0, 0.0));
+        assert_near(Vector::new(2.0, 0.0).rotate(Radians(PI / 2.0)), Vector::new(0.0, 2.0));
+        assert!((Vector::new(0.0, -3.0).heading().0 + PI / 2.0).abs() < 1e-6);
+        assert!((Vector::new(1.0, 0.0).get_angle(&Vector::new(0.0, 1.0)).0 - PI / 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn measures_lengths_and_distances() {
+        let vector = Vector::new(3.0, -4.0);
+        assert_eq!(vector.length_sq(), 25.0);
+        assert_eq!(vector.module(), 5.0);
+        assert_eq!(Vector::new(1.0, 1.0).distance(&Vector::new(4.0, 5.0)), 5.0);
+        assert_eq!(vector.dot_product(&Vector::new(2.0, 1.0)), 2.0);
+        assert_eq!(vector.determinant(&Vector::new(2.0, 1.0)), 11.0);
+    }
+
+    #[test]
+    fn interpolates_between_points() {
+        let start = Vector::new(1000.0, 2000.0);
+        let end = Vector::new(3000.0, 1000.0);
+        assert_eq!(start.lerp(&end, 0.0), start);
+        assert_eq!(start.lerp(&end, 0.5), Vector::new(2000.0, 1500.0));
+        assert_eq!(start.lerp(&end, 1.0), end);
+        assert_eq!(start.lerp(&end, 2.0), Vector::new(5000.0, 0.0));
+    }
+
+    #[test]
+    fn combines_with_operators() {
+        let a = Vector::new(1.0, 2.0);
+        let b = Vector::new(-3.0, 0.5);
+        assert_eq!(a + b, Vector::new(-2.0, 2.5));
+        assert_eq!(a - b, Vector::new(4.0, 1.5));
+        assert_eq!(a * 3.0, Vector::new(3.0, 6.0));
+        assert_eq!(-a, Vector::new(-1.0, -2.0));
+        assert!(Vector::zero().is_zero() && !a.is_zero());
+        assert_eq!(Vector::new(12.7, -3.2).to_position(), Position::new(12, -3));
+        assert_eq!(Vector::from(&Position::new(-5, 8)), Vector::new(-5.0, 8.0));
+    }
+}
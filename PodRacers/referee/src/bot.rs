@@ -0,0 +1,54 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command as Process, Stdio};
+
+use podracer_core::command::Command;
+
+// #########################################
+// ##                 Bot                 ##
+// #########################################
+// A bot binary driven over its stdin/stdout, like on the CodinGame servers.
+pub struct Bot {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+impl Bot {
+    // Runs the command line through the shell, so arguments can be given inline
+    pub fn spawn(command_line: &str, show_stderr: bool) -> io::Result<Bot> {
+        let mut child = Process::new("sh")
+            .arg("-c")
+            .arg(command_line)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(if show_stderr { Stdio::inherit() } else { Stdio::null() })
+            .spawn()?;
+        let stdin = child.stdin.take().unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+
+        Ok(Bot { child, stdin, stdout })
+    }
+    pub fn send(&mut self, lines: &[String]) -> io::Result<()> {
+        for line in lines {
+            writeln!(self.stdin, "{}", line)?;
+        }
+        self.stdin.flush()
+    }
+    pub fn read_commands(&mut self, count: usize) -> Result<Vec<Command>, String> {
+        let mut commands = Vec::with_capacity(count);
+        for _ in 0..count {
+            let mut line = String::new();
+            match self.stdout.read_line(&mut line) {
+                Ok(0) => return Err("bot closed its output".to_string()),
+                Ok(_) => commands.push(line.parse::<Command>()?),
+                Err(error) => return Err(error.to_string()),
+            }
+        }
+        Ok(commands)
+    }
+}
+impl Drop for Bot {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
@@ -0,0 +1,274 @@
+
+use podracer_core::angle::{Degrees, Radians};
+use podracer_core::command::Command;
+use podracer_core::physics;
+use podracer_core::pod::PodInfo;
+use podracer_core::simulation::play_turn;
+use podracer_core::track::Track;
+use podracer_core::vector::{Scalar, Vector, PI};
+
+// #########################################
+// ##                Mode                 ##
+// #########################################
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+    // Wood leagues protocol, as read by single_pot.rs
+    SinglePod,
+    // Two pods per player, as read by multiple_pods.rs
+    MultiPod,
+}
+impl Mode {
+    pub fn pods_per_player(self) -> usize {
+        match self {
+            Mode::SinglePod => 1,
+            Mode::MultiPod => 2,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Outcome {
+    Winner(usize),
+    Draw,
+}
+
+// #########################################
+// ##                Game                 ##
+// #########################################
+pub struct Game {
+    pub mode: Mode,
+    pub track: Track,
+    // Pods of player 1, then pods of player 2
+    pub pods: Vec<PodInfo>,
+    pub turn: usize,
+    // First player to complete the race
+    finisher: Option<usize>,
+}
+impl Game {
+    pub fn new(track: Track, mode: Mode) -> Game {
+        let start = &track.checkpoints[0];
+        let first = &track.checkpoints[1];
+        let side = (*first - *start).normalize_or_zero().rotate(Radians(PI / 2.0));
+
+        // Pods line up on checkpoint 0, across the way to checkpoint 1
+        let offsets: &[Scalar] = match mode {
+            Mode::SinglePod => &[500.0, -500.0],
+            Mode::MultiPod => &[500.0, -500.0, 1500.0, -1500.0],
+        };
+        let pods = offsets
+            .iter()
+            .map(|offset| {
+                let position = *start + side * *offset;
+                let position = Vector::new(physics::round(position.x), physics::round(position.y));
+                let angle = Degrees(physics::round(physics::angle_to(&position, first).0)).heading();
+                PodInfo::new(position.x, position.y, 0.0, 0.0, angle, 1)
+            })
+            .collect();
+
+        Game {
+            mode,
+            track,
+            pods,
+            turn: 0,
+            finisher: None,
+        }
+    }
+
+    pub fn player_pods(&self, player: usize) -> &[PodInfo] {
+        let count = self.mode.pods_per_player();
+        &self.pods[player * count..(player + 1) * count]
+    }
+
+    // +--------------------------+
+    // |          Inputs          |
+    // +--------------------------+
+    pub fn initial_input(&self) -> Vec<String> {
+        match self.mode {
+            Mode::SinglePod => Vec::new(),
+            Mode::MultiPod => {
+                let mut lines = vec![self.track.laps.to_string(), self.track.checkpoints.len().to_string()];
+                for checkpoint in &self.track.checkpoints {
+                    lines.push(format!("{} {}", checkpoint.x, checkpoint.y));
+                }
+                lines
+            }
+        }
+    }
+
+    pub fn turn_input(&self, player: usize) -> Vec<String> {
+        let opponent = 1 - player;
+        match self.mode {
+            Mode::SinglePod => {
+                let pod = &self.player_pods(player)[0];
+                let checkpoint = &self.track.checkpoints[pod.next_checkpoint];
+                let distance = checkpoint.distance(&pod.position);
+                let angle = physics::diff_angle(pod, checkpoint);
+                let enemy = &self.player_pods(opponent)[0];
+                vec![
+                    format!(
+                        "{} {} {} {} {} {}",
+                        pod.position.x,
+                        pod.position.y,
+                        checkpoint.x,
+                        checkpoint.y,
+                        physics::round(distance),
+                        physics::round(angle.0)
+                    ),
+                    format!("{} {}", enemy.position.x, enemy.position.y),
+                ]
+            }
+            // Like the official referee, no heading is given before the first
+            // turn, where rotation is free
+            Mode::MultiPod => self
+                .player_pods(player)
+                .iter()
+                .chain(self.player_pods(opponent))
+                .map(|pod| {
+                    let angle = if self.turn == 0 { "-1".to_string() } else { pod.angle.to_string() };
+                    format!(
+                        "{} {} {} {} {} {}",
+                        pod.position.x, pod.position.y, pod.speed.x, pod.speed.y, angle, pod.next_checkpoint
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    // +--------------------------+
+    // |      Turn resolution     |
+    // +--------------------------+
+    // Applies one command per pod, indexed by player then pod
+    pub fn play_turn(&mut self, commands: &[Vec<Command>]) {
+        let commands = commands.concat();
+        let finisher = play_turn(&self.track, &mut self.pods, &commands, self.turn == 0);
+        if self.finisher.is_none() {
+            self.finisher = finisher.map(|pod| pod / self.mode.pods_per_player());
+        }
+        self.turn += 1;
+    }
+
+    // +--------------------------+
+    // |          Outcome         |
+    // +--------------------------+
+    pub fn outcome(&self) -> Option<Outcome> {
+        if let Some(player) = self.finisher {
+            return Some(Outcome::Winner(player));
+        }
+        // A player times out once none of its pods has passed a checkpoint in time
+        let timed_out = (0..2)
+            .map(|player| self.player_pods(player).iter().all(|pod| pod.timeout <= 0))
+            .collect::<Vec<_>>();
+        match (timed_out[0], timed_out[1]) {
+            (true, true) => Some(Outcome::Draw),
+            (true, false) => Some(Outcome::Winner(1)),
+            (false, true) => Some(Outcome::Winner(0)),
+            (false, false) => None,
+        }
+    }
+
+    // Player furthest in the race, used when the game is cut short
+    pub fn leader(&self) -> Outcome {
+        let progress = (0..2)
+            .map(|player| {
+                self.player_pods(player)
+                    .iter()
+                    .map(|pod| {
+                        let checkpoint = &self.track.checkpoints[pod.next_checkpoint];
+                        let distance = checkpoint.distance(&pod.position);
+                        (pod.checkpoints_passed, -distance)
+                    })
+                    .fold((0, Scalar::MIN), |best, progress| if progress > best { progress } else { best })
+            })
+            .collect::<Vec<_>>();
+
+        if progress[0] > progress[1] {
+            Outcome::Winner(0)
+        } else if progress[1] > progress[0] {
+            Outcome::Winner(1)
+        } else {
+            Outcome::Draw
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use podracer_core::command::Action;
+    use podracer_core::position::Position;
+
+    fn track() -> Track {
+        Track::new(3, vec![Vector::new(1000.0, 1000.0), Vector::new(9000.0, 1000.0), Vector::new(9000.0, 8000.0)])
+    }
+
+    #[test]
+    fn lines_pods_up_facing_checkpoint_one() {
+        // Checkpoint 1 just below the east, 359.6 degrees from the pod at 500
+        let track = Track::new(3, vec![Vector::new(0.0, 0.0), Vector::new(71620.0, 0.0), Vector::new(0.0, 8000.0)]);
+        let game = Game::new(track, Mode::MultiPod);
+        assert_eq!(game.pods[0].position, Vector::new(0.0, 500.0));
+        assert_eq!(game.pods[0].angle, Degrees(0.0));
+        assert!(game.pods.iter().all(|pod| pod.angle.0 < 360.0));
+    }
+
+    #[test]
+    fn writes_the_single_pod_protocol() {
+        let game = Game::new(track(), Mode::SinglePod);
+        assert!(game.initial_input().is_empty());
+        // 8016 away, already facing checkpoint 1 to the nearest degree
+        assert_eq!(game.turn_input(0), ["1000 1500 9000 1000 8016 0", "1000 500"]);
+        assert_eq!(game.turn_input(1), ["1000 500 9000 1000 8016 0", "1000 1500"]);
+    }
+
+    #[test]
+    fn writes_the_multi_pod_protocol() {
+        let mut game = Game::new(track(), Mode::MultiPod);
+        assert_eq!(game.initial_input(), ["3", "3", "1000 1000", "9000 1000", "9000 8000"]);
+        // Player 2 sees its own pods first
+        assert_eq!(
+            game.turn_input(1),
+            ["1000 2500 0 0 -1 1", "1000 -500 0 0 -1 1", "1000 1500 0 0 -1 1", "1000 500 0 0 -1 1"]
+        );
+
+        let command = Command::new(Position::new(9000, 1000), Action::Thrust(0));
+        game.play_turn(&[vec![command.clone(), command.clone()], vec![command.clone(), command]]);
+        let line = &game.turn_input(0)[0];
+        assert_eq!(line, &format!("1000 1500 0 0 {} 1", game.pods[0].angle));
+    }
+
+    #[test]
+    fn ends_when_a_player_has_no_pod_left_in_time() {
+        let mut game = Game::new(track(), Mode::MultiPod);
+        assert_eq!(game.outcome(), None);
+
+        game.pods[0].timeout = 0;
+        assert_eq!(game.outcome(), None);
+        game.pods[1].timeout = 0;
+        assert_eq!(game.outcome(), Some(Outcome::Winner(1)));
+        game.pods[3].timeout = -1;
+        game.pods[2].timeout = 0;
+        assert_eq!(game.outcome(), Some(Outcome::Draw));
+
+        // Finishing the race wins, whatever the timeouts
+        game.finisher = Some(0);
+        assert_eq!(game.outcome(), Some(Outcome::Winner(0)));
+    }
+
+    #[test]
+    fn ranks_players_by_their_best_pod() {
+        let mut game = Game::new(track(), Mode::MultiPod);
+        // Player 1's pods lined up nearer the middle
+        assert_eq!(game.leader(), Outcome::Winner(0));
+        game.pods[2].position = game.pods[0].position;
+        assert_eq!(game.leader(), Outcome::Draw);
+
+        // Closer to the same checkpoint
+        game.pods[3].position = Vector::new(5000.0, 1000.0);
+        assert_eq!(game.leader(), Outcome::Winner(1));
+
+        // A checkpoint more, however far from the next one
+        game.pods[0].checkpoints_passed = 1;
+        game.pods[0].next_checkpoint = 2;
+        assert_eq!(game.leader(), Outcome::Winner(0));
+    }
+}
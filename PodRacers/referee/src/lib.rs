@@ -0,0 +1,5 @@
+// Local referee for Mad Pod Racing: maps, turn resolution and bot processes.
+
+pub mod bot;
+pub mod game;
+pub mod map;
@@ -0,0 +1,123 @@
+use std::env;
+use std::process;
+
+use referee::bot::Bot;
+use referee::game::{Game, Mode, Outcome};
+use referee::map;
+
+const DEFAULT_MAX_TURNS: usize = 600;
+
+const USAGE: &str = "usage: referee [--single] [--seed N] [--laps N] [--max-turns N] [--verbose] <bot 1> <bot 2>";
+
+struct Options {
+    mode: Mode,
+    seed: u64,
+    laps: Option<usize>,
+    max_turns: usize,
+    verbose: bool,
+    bots: Vec<String>,
+}
+
+fn parse_options() -> Result<Options, String> {
+    let mut options = Options {
+        mode: Mode::MultiPod,
+        seed: 0,
+        laps: None,
+        max_turns: DEFAULT_MAX_TURNS,
+        verbose: false,
+        bots: Vec::new(),
+    };
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("missing value for {}", name));
+        match arg.as_str() {
+            "--single" => options.mode = Mode::SinglePod,
+            "--verbose" => options.verbose = true,
+            "--seed" => options.seed = value("--seed")?.parse().map_err(|_| "invalid --seed")?,
+            "--laps" => options.laps = Some(value("--laps")?.parse().map_err(|_| "invalid --laps")?),
+            "--max-turns" => options.max_turns = value("--max-turns")?.parse().map_err(|_| "invalid --max-turns")?,
+            _ => options.bots.push(arg),
+        }
+    }
+
+    if options.bots.len() != 2 {
+        return Err(USAGE.to_string());
+    }
+    Ok(options)
+}
+
+fn main() {
+    let options = parse_options().unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        process::exit(2);
+    });
+
+    let mut track = map::generate(options.seed);
+    if let Some(laps) = options.laps {
+        track.laps = laps;
+    }
+    let mut game = Game::new(track, options.mode);
+
+    let mut bots = options
+        .bots
+        .iter()
+        .map(|command_line| {
+            Bot::spawn(command_line, options.verbose).unwrap_or_else(|error| {
+                eprintln!("cannot start \"{}\": {}", command_line, error);
+                process::exit(1);
+            })
+        })
+        .collect::<Vec<_>>();
+
+    println!("seed {}: {} checkpoints, {} laps", options.seed, game.track.checkpoints.len(), game.track.laps);
+    for bot in &mut bots {
+        // A bot failing here will also fail reading its first commands
+        let _ = bot.send(&game.initial_input());
+    }
+
+    let outcome = loop {
+        if let Some(outcome) = game.outcome() {
+            break outcome;
+        }
+        if game.turn >= options.max_turns {
+            break game.leader();
+        }
+
+        let mut commands = Vec::with_capacity(bots.len());
+        let mut failed = None;
+        for (player, bot) in bots.iter_mut().enumerate() {
+            let result = bot
+                .send(&game.turn_input(player))
+                .map_err(|error| error.to_string())
+                .and_then(|_| bot.read_commands(game.mode.pods_per_player()));
+            match result {
+                Ok(player_commands) => commands.push(player_commands),
+                Err(error) => {
+                    println!("player {} disqualified on turn {}: {}", player + 1, game.turn, error);
+                    failed = Some(player);
+                    break;
+                }
+            }
+        }
+        if let Some(player) = failed {
+            break Outcome::Winner(1 - player);
+        }
+
+        game.play_turn(&commands);
+
+        if options.verbose {
+            eprintln!("turn {}", game.turn);
+            for player in 0..2 {
+                for pod in game.player_pods(player) {
+                    eprintln!("  player {}: {}", player + 1, pod);
+                }
+            }
+        }
+    };
+
+    match outcome {
+        Outcome::Winner(player) => println!("player {} wins after {} turns", player + 1, game.turn),
+        Outcome::Draw => println!("draw after {} turns", game.turn),
+    }
+}
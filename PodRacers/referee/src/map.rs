@@ -0,0 +1,36 @@
+use podracer_core::constants::{MAP_HEIGHT, MAP_WIDTH};
+use podracer_core::rng::Rng;
+use podracer_core::track::Track;
+use podracer_core::vector::{Scalar, Vector};
+
+// #########################################
+// ##                 Map                 ##
+// #########################################
+const MIN_CHECKPOINTS: i32 = 3;
+const MAX_CHECKPOINTS: i32 = 8;
+const DEFAULT_LAPS: usize = 3;
+// Checkpoints are kept away from the borders and from each other
+const BORDER_MARGIN: i32 = 1200;
+const MIN_CHECKPOINT_GAP: Scalar = 2500.0;
+
+// Random track, always the same for a given seed
+pub fn generate(seed: u64) -> Track {
+    let mut rng = Rng::new(seed);
+    let count = rng.range(MIN_CHECKPOINTS, MAX_CHECKPOINTS) as usize;
+
+    let mut checkpoints: Vec<Vector> = Vec::with_capacity(count);
+    while checkpoints.len() < count {
+        let candidate = Vector::new(
+            rng.range(BORDER_MARGIN, MAP_WIDTH as i32 - BORDER_MARGIN) as Scalar,
+            rng.range(BORDER_MARGIN, MAP_HEIGHT as i32 - BORDER_MARGIN) as Scalar,
+        );
+        if checkpoints
+            .iter()
+            .all(|checkpoint| checkpoint.distance(&candidate) >= MIN_CHECKPOINT_GAP)
+        {
+            checkpoints.push(candidate);
+        }
+    }
+
+    Track::new(DEFAULT_LAPS, checkpoints)
+}